@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use nalgebra::Vector3;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use spherical_cow::shapes::TriMesh;
+use spherical_cow::util::ray_intersection_count;
+
+fn ray_count_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let dirs: Vec<Vector3<f32>> = (0..100)
+        .map(|_| {
+            Vector3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+            .normalize()
+        })
+        .collect();
+
+    let mut rays = c.benchmark_group("ray intersection count");
+    for name in ["emerald", "cow"].iter() {
+        let mesh = TriMesh::load_obj(format!("examples/objects/{}.obj", name)).unwrap();
+        let (triangles, bvh) = (mesh.triangles(), mesh.bvh());
+        rays.bench_with_input(BenchmarkId::new("brute force", name), name, |b, _| {
+            b.iter(|| {
+                dirs.iter()
+                    .map(|dir| ray_intersection_count(triangles, *dir, 10.))
+                    .sum::<i32>()
+            });
+        });
+        rays.bench_with_input(BenchmarkId::new("bvh", name), name, |b, _| {
+            b.iter(|| {
+                dirs.iter()
+                    .map(|dir| bvh.ray_intersection_count(*dir, 10.))
+                    .sum::<i32>()
+            });
+        });
+    }
+    rays.finish();
+}
+
+criterion_group!(benches, ray_count_benchmark);
+criterion_main!(benches);
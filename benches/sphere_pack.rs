@@ -1,7 +1,10 @@
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use nalgebra::Point3;
 use rand::distributions::Uniform;
-use spherical_cow::shapes::Sphere;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use spherical_cow::shapes::{Cuboid, Sphere};
+use spherical_cow::SpatialGrid;
 
 fn sphere_benchmark(c: &mut Criterion) {
     let mut sphere = c.benchmark_group("sphere 0.1-0.2");
@@ -18,5 +21,87 @@ fn sphere_benchmark(c: &mut Criterion) {
     sphere.finish();
 }
 
-criterion_group!(benches, sphere_benchmark);
+fn cuboid_scaling_benchmark(c: &mut Criterion) {
+    // Large beds: the time per unit volume should stay roughly flat as the
+    // cuboid grows, since neighbourhood queries no longer scan every packed sphere.
+    let mut cuboid = c.benchmark_group("cuboid scaling 0.1-0.2");
+    cuboid.sample_size(10);
+    for half_extent in [1f32, 2., 3., 4.].iter() {
+        let volume = (2. * half_extent).powi(3);
+        cuboid.throughput(Throughput::Elements(volume as u64));
+        cuboid.bench_with_input(
+            BenchmarkId::from_parameter(half_extent),
+            half_extent,
+            |b, &half_extent| {
+                b.iter(|| {
+                    let boundary = Cuboid::new(half_extent, half_extent, half_extent).unwrap();
+                    let mut sizes = Uniform::new(0.1, 0.2);
+
+                    let _spheres = spherical_cow::pack_spheres(&boundary, &mut sizes).unwrap();
+                });
+            },
+        );
+    }
+    cuboid.finish();
+}
+
+fn neighbour_query_benchmark(c: &mut Criterion) {
+    // Finds the neighbourhood of every sphere in packings of growing size. Scanning every
+    // sphere grows quadratically with the packing, the spatial grid only linearly.
+    let mut queries = c.benchmark_group("neighbour queries 0.1-0.2");
+    for half_extent in [1f32, 2., 3., 4.].iter() {
+        let boundary = Cuboid::new(*half_extent, *half_extent, *half_extent).unwrap();
+        let mut sizes = Uniform::new(0.1, 0.2);
+        let mut rng = StdRng::seed_from_u64(0);
+        let spheres =
+            spherical_cow::pack_spheres_with_rng(&boundary, &mut sizes, &mut rng).unwrap();
+        // As used while packing: a query reaches two diameters of the largest new sphere
+        let reach = |sphere: &Sphere| sphere.radius + 0.4;
+        let in_reach = |sphere: &Sphere, other: &Sphere| {
+            nalgebra::distance(&sphere.center, &other.center) <= reach(sphere) + other.radius
+        };
+
+        queries.throughput(Throughput::Elements(spheres.len() as u64));
+        queries.bench_with_input(
+            BenchmarkId::new("brute force", half_extent),
+            &spheres,
+            |b, spheres| {
+                b.iter(|| {
+                    spheres
+                        .iter()
+                        .map(|sphere| spheres.iter().filter(|s| in_reach(sphere, s)).count())
+                        .sum::<usize>()
+                });
+            },
+        );
+        queries.bench_with_input(
+            BenchmarkId::new("spatial grid", half_extent),
+            &spheres,
+            |b, spheres| {
+                let grid = SpatialGrid::from_spheres(spheres, 0.6);
+                let mut found = Vec::new();
+                b.iter(|| {
+                    spheres
+                        .iter()
+                        .map(|sphere| {
+                            grid.neighbours(&sphere.center, reach(sphere), &mut found);
+                            found
+                                .iter()
+                                .filter(|&&idx| in_reach(sphere, &spheres[idx]))
+                                .count()
+                        })
+                        .sum::<usize>()
+                });
+            },
+        );
+    }
+    queries.finish();
+}
+
+criterion_group!(
+    benches,
+    sphere_benchmark,
+    cuboid_scaling_benchmark,
+    neighbour_query_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,375 @@
+//! Fine grained control over a packing run.
+//!
+//! [pack_spheres](../fn.pack_spheres.html) only returns once no more spheres fit in the container,
+//! which can take a long time for large geometries. A `PackingBuilder` lets you stop early once
+//! some criterion is met, and reports which criterion ended the run.
+//!
+//! ```rust,no_run
+//! use spherical_cow::builder::{PackingBuilder, Termination};
+//! use spherical_cow::shapes::Cuboid;
+//! use rand::distributions::Uniform;
+//! use std::time::Duration;
+//!
+//! let boundary = Cuboid::new(10., 10., 10.).unwrap();
+//! let mut sizes = Uniform::new(0.1, 0.2);
+//!
+//! let packing = PackingBuilder::new(boundary)
+//!     .volume_fraction(0.4)
+//!     .time_budget(Duration::from_secs(60))
+//!     .pack(&mut sizes)
+//!     .unwrap();
+//!
+//! if packing.termination == Termination::TimeBudget {
+//!     println!("Ran out of time at {:.2}%", packing.packed.volume_fraction() * 100.);
+//! }
+//! ```
+
+use crate::errors::SphericalCowError as Error;
+#[cfg(feature = "rayon")]
+use crate::parallel::advance_front_parallel;
+use crate::shapes::Sphere;
+use crate::{advance_front, init_front, Container, PackedVolume};
+use rand::distributions::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::mem;
+use std::time::Duration;
+
+/// The reason a packing run came to an end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// The advancing front emptied: no further spheres can be placed in the container.
+    FrontExhausted,
+    /// The requested number of spheres has been packed.
+    SphereCount,
+    /// The requested volume fraction has been reached.
+    VolumeFraction,
+    /// The wall-clock budget has been spent.
+    TimeBudget,
+    /// The maximum number of front iterations has been performed.
+    Iterations,
+    /// A [PackingObserver](trait.PackingObserver.html) asked for the run to stop.
+    Cancelled,
+}
+
+/// Returned by a [PackingObserver](trait.PackingObserver.html) to steer the current run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Keep packing.
+    Continue,
+    /// Stop as soon as possible, keeping all spheres packed so far.
+    Cancel,
+}
+
+/// A snapshot of how far a packing run has progressed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Number of spheres packed so far.
+    pub spheres: usize,
+    /// Number of spheres remaining in the advancing front.
+    pub front: usize,
+    /// Volume fraction of the packing so far.
+    pub volume_fraction: f32,
+    /// Number of front iterations performed in this run.
+    pub iterations: usize,
+}
+
+/// Receives progress updates during a packing run, and may cancel it. Both methods default
+/// to doing nothing, so implementors need only override the events they care about.
+///
+/// ```rust,no_run
+/// use spherical_cow::builder::{Control, PackingBuilder, PackingObserver, Progress};
+/// use spherical_cow::shapes::Sphere;
+/// use rand::distributions::Uniform;
+/// use nalgebra::Point3;
+///
+/// struct Reporter;
+///
+/// impl PackingObserver for Reporter {
+///     fn sphere_placed(&mut self, progress: &Progress) -> Control {
+///         if progress.spheres % 1000 == 0 {
+///             println!("{} spheres, {:.2}%", progress.spheres, progress.volume_fraction * 100.);
+///         }
+///         Control::Continue
+///     }
+/// }
+///
+/// let boundary = Sphere::new(Point3::origin(), 10.).unwrap();
+/// let mut sizes = Uniform::new(0.1, 0.2);
+/// let mut reporter = Reporter;
+/// let packing = PackingBuilder::new(boundary)
+///     .observer(&mut reporter)
+///     .pack(&mut sizes)
+///     .unwrap();
+/// ```
+pub trait PackingObserver {
+    /// Called each time a new sphere is added to the packing.
+    fn sphere_placed(&mut self, _progress: &Progress) -> Control {
+        Control::Continue
+    }
+
+    /// Called each time a sphere leaves the front, as there is no room left around it.
+    fn sphere_exhausted(&mut self, _progress: &Progress) -> Control {
+        Control::Continue
+    }
+}
+
+/// Criteria which may end a packing run before the front is exhausted.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Limits {
+    pub(crate) max_spheres: Option<usize>,
+    pub(crate) volume_fraction: Option<f32>,
+    pub(crate) time_budget: Option<Duration>,
+    pub(crate) max_iterations: Option<usize>,
+}
+
+/// The outcome of a [PackingBuilder](struct.PackingBuilder.html) run.
+#[derive(Debug)]
+pub struct Packing<C> {
+    /// The spheres packed so far, along with their container.
+    pub packed: PackedVolume<C>,
+    /// Indices into `packed.spheres` of all spheres still on the advancing front.
+    /// Saving this alongside the packing allows a run to be resumed without
+    /// revisiting spheres which have already been exhausted.
+    pub front: Vec<usize>,
+    /// Which criterion ended the run.
+    pub termination: Termination,
+}
+
+impl<C: Container> Packing<C> {
+    /// Prepares to continue this packing from where it stopped. Any criteria or observer
+    /// must be set again on the returned builder.
+    pub fn resume<'a>(self) -> PackingBuilder<'a, C> {
+        PackingBuilder::resume(self.packed).front(self.front)
+    }
+}
+
+/// Configures and runs a packing. Without any criteria set, this behaves exactly like
+/// [PackedVolume::new](../struct.PackedVolume.html#method.new). When multiple criteria are
+/// set, the run ends as soon as any one of them is met.
+pub struct PackingBuilder<'a, C> {
+    container: C,
+    limits: Limits,
+    seed: Option<u64>,
+    observer: Option<&'a mut dyn PackingObserver>,
+    spheres: Vec<Sphere>,
+    front: Option<Vec<usize>>,
+    #[cfg(feature = "rayon")]
+    batch_size: Option<usize>,
+}
+
+impl<'a, C: Container> PackingBuilder<'a, C> {
+    /// Starts configuring a packing into `container`.
+    pub fn new(container: C) -> PackingBuilder<'a, C> {
+        PackingBuilder {
+            container,
+            limits: Limits::default(),
+            seed: None,
+            observer: None,
+            spheres: Vec::new(),
+            front: None,
+            #[cfg(feature = "rayon")]
+            batch_size: None,
+        }
+    }
+
+    /// Continues packing on top of the spheres already held in `packed`, for example
+    /// one loaded from a checkpoint. Unless a saved [front](#method.front) is given,
+    /// every sphere in the packing is treated as part of the advancing front. Resuming
+    /// from an empty set of spheres starts a fresh packing. New spheres are placed tangent
+    /// to three existing ones, so `packed` should come from an earlier run of the advancing
+    /// front (or otherwise contain spheres in contact) for the packing to grow.
+    ///
+    /// Note that a resumed run, even when seeded, will not be identical to an
+    /// uninterrupted one as the random state is not part of the checkpoint.
+    pub fn resume(packed: PackedVolume<C>) -> PackingBuilder<'a, C> {
+        PackingBuilder {
+            spheres: packed.spheres,
+            ..PackingBuilder::new(packed.container)
+        }
+    }
+
+    /// Restores a saved advancing `front`: distinct indices into the spheres given to
+    /// [resume](#method.resume), as found in [Packing::front](struct.Packing.html#structfield.front).
+    pub fn front(mut self, front: Vec<usize>) -> PackingBuilder<'a, C> {
+        self.front = Some(front);
+        self
+    }
+
+    /// Stop once `count` spheres have been packed.
+    pub fn max_spheres(mut self, count: usize) -> PackingBuilder<'a, C> {
+        self.limits.max_spheres = Some(count);
+        self
+    }
+
+    /// Stop once the packed spheres fill at least `fraction` of the container's volume.
+    pub fn volume_fraction(mut self, fraction: f32) -> PackingBuilder<'a, C> {
+        self.limits.volume_fraction = Some(fraction);
+        self
+    }
+
+    /// Stop once `budget` of wall-clock time has elapsed. This is checked once per front
+    /// iteration, so runs may overshoot the budget by the duration of one iteration.
+    pub fn time_budget(mut self, budget: Duration) -> PackingBuilder<'a, C> {
+        self.limits.time_budget = Some(budget);
+        self
+    }
+
+    /// Stop after `count` iterations of the advancing front. Each iteration either places a
+    /// sphere or removes one from the front.
+    pub fn max_iterations(mut self, count: usize) -> PackingBuilder<'a, C> {
+        self.limits.max_iterations = Some(count);
+        self
+    }
+
+    /// Seed the random number generator used by [pack](#method.pack), making the run reproducible.
+    pub fn seed(mut self, seed: u64) -> PackingBuilder<'a, C> {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Report progress to `observer` as the packing grows. The observer may cancel the run,
+    /// in which case the spheres packed so far are returned.
+    pub fn observer<O: PackingObserver>(mut self, observer: &'a mut O) -> PackingBuilder<'a, C> {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Packs spheres with radii drawn from `size_distribution` until the front is exhausted or a
+    /// criterion is met. Uses a seeded generator if [seed](#method.seed) was set, otherwise
+    /// `rand::thread_rng()`.
+    pub fn pack<D: Distribution<f64>>(
+        self,
+        size_distribution: &mut D,
+    ) -> Result<Packing<C>, Error> {
+        match self.seed {
+            Some(seed) => self.pack_with_rng(size_distribution, &mut StdRng::seed_from_u64(seed)),
+            None => self.pack_with_rng(size_distribution, &mut rand::thread_rng()),
+        }
+    }
+
+    /// Identical to [pack](#method.pack), but all random choices are drawn from `rng`.
+    pub fn pack_with_rng<D: Distribution<f64>, R: Rng + ?Sized>(
+        mut self,
+        size_distribution: &mut D,
+        rng: &mut R,
+    ) -> Result<Packing<C>, Error> {
+        let (mut spheres, mut front) = self.initial_front(size_distribution, rng)?;
+        let termination = advance_front(
+            &self.container,
+            size_distribution,
+            rng,
+            &mut spheres,
+            &mut front,
+            &self.limits,
+            self.observer,
+        )?;
+        Ok(Packing {
+            packed: PackedVolume::from_vec(spheres, self.container),
+            front,
+            termination,
+        })
+    }
+
+    /// The spheres and front to begin packing from: either those handed to
+    /// [resume](#method.resume), or a fresh set of three initial spheres.
+    fn initial_front<D: Distribution<f64>, R: Rng + ?Sized>(
+        &mut self,
+        size_distribution: &mut D,
+        rng: &mut R,
+    ) -> Result<(Vec<Sphere>, Vec<usize>), Error> {
+        let spheres = mem::take(&mut self.spheres);
+        let front = self.front.take();
+        if spheres.is_empty() {
+            if front.map_or(false, |front| !front.is_empty()) {
+                return Err(Error::InvalidFront);
+            }
+            return init_front(&self.container, size_distribution, rng);
+        }
+        let front = match front {
+            Some(front) => {
+                // Each sphere may only be on the front once, or it would be removed twice
+                let mut unique = front.clone();
+                unique.sort_unstable();
+                unique.dedup();
+                if unique.len() != front.len() || front.iter().any(|&idx| idx >= spheres.len()) {
+                    return Err(Error::InvalidFront);
+                }
+                front
+            }
+            None => (0..spheres.len()).collect(),
+        };
+        Ok((spheres, front))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, C: Container + Sync> PackingBuilder<'a, C> {
+    /// Sets how many front spheres [pack_parallel](#method.pack_parallel) works on at once.
+    /// Defaults to four per thread in the current `rayon` pool.
+    pub fn batch_size(mut self, batch_size: usize) -> PackingBuilder<'a, C> {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Packs spheres using all threads of the current `rayon` pool. Many front spheres are
+    /// searched at once, and placements are committed in order such that the result is
+    /// still overlap free, contained, and reproducible when [seed](#method.seed) is set.
+    /// The packing will differ from the one [pack](#method.pack) produces with the same seed.
+    pub fn pack_parallel<D: Distribution<f64>>(
+        self,
+        size_distribution: &mut D,
+    ) -> Result<Packing<C>, Error> {
+        match self.seed {
+            Some(seed) => {
+                self.pack_parallel_with_rng(size_distribution, &mut StdRng::seed_from_u64(seed))
+            }
+            None => self.pack_parallel_with_rng(size_distribution, &mut rand::thread_rng()),
+        }
+    }
+
+    /// Identical to [pack_parallel](#method.pack_parallel), but all random choices are drawn
+    /// from `rng`.
+    pub fn pack_parallel_with_rng<D: Distribution<f64>, R: Rng + ?Sized>(
+        mut self,
+        size_distribution: &mut D,
+        rng: &mut R,
+    ) -> Result<Packing<C>, Error> {
+        let (mut spheres, mut front) = self.initial_front(size_distribution, rng)?;
+        let batch_size = self
+            .batch_size
+            .unwrap_or_else(|| 4 * rayon::current_num_threads());
+        let termination = advance_front_parallel(
+            &self.container,
+            size_distribution,
+            rng,
+            &mut spheres,
+            &mut front,
+            &self.limits,
+            self.observer,
+            batch_size,
+        )?;
+        Ok(Packing {
+            packed: PackedVolume::from_vec(spheres, self.container),
+            front,
+            termination,
+        })
+    }
+}
+
+impl<'a, C: fmt::Debug> fmt::Debug for PackingBuilder<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("PackingBuilder");
+        debug
+            .field("container", &self.container)
+            .field("limits", &self.limits)
+            .field("seed", &self.seed)
+            .field("observer", &self.observer.is_some())
+            .field("spheres", &self.spheres)
+            .field("front", &self.front);
+        #[cfg(feature = "rayon")]
+        debug.field("batch_size", &self.batch_size);
+        debug.finish()
+    }
+}
@@ -10,11 +10,46 @@ pub enum SphericalCowError {
     NegativeRadius,
     /// If a cuboid is given a negative half extent.
     NegativeExtents,
+    /// If a cuboid is given other than three half extents.
+    ExtentCount,
+    /// If a shape is given a negative height.
+    NegativeHeight,
+    /// If a shell is given an inner radius which is not smaller than its outer radius.
+    InvertedShell,
+    /// If a combination of containers needs the bounding box of a container which does not
+    /// report one.
+    Unbounded,
+    /// If a transformed container is given a scale which is not positive.
+    NegativeScale,
+    /// If a volume is to be estimated on a grid without any cells, or from no samples.
+    NoSamples,
+    /// If a volume is to be estimated on a grid with more cells than can be counted.
+    TooManyCells,
+    /// If a face of a triangle mesh refers to a vertex which does not exist, or uses the same
+    /// vertex more than once.
+    InvalidFace,
+    /// If a triangle mesh has an edge which is not shared by exactly two faces, so does not
+    /// enclose a volume.
+    OpenMesh,
+    /// If neighbouring faces of a triangle mesh wind in opposite directions, so do not agree on
+    /// which side is the inside.
+    InconsistentOrientation,
+    /// If a triangle mesh encloses no volume, or is to be rescaled but has no volume or extent to
+    /// scale.
+    DegenerateMesh,
+    /// If a mesh file cannot be opened or read.
+    MeshRead,
+    /// If a mesh file cannot be parsed in the format it was read as.
+    MalformedMesh,
+    /// If a mesh file contains a face with other than three vertices.
+    NonTriangularFace,
     /// If a sphere is created but is not confined by the `Container`.
     /// This happens quite a lot and is generally handled silently. This error
-    /// is only thrown by the `init_spheres` method. Usually this means the geometry
-    /// of the container is perhaps not aligned to the origin, it is scaled too small,
-    /// or the spheres you're attempting to pack are too large.
+    /// is only thrown by the `init_spheres` method, when the initial spheres fit neither
+    /// about the container's `seed_point` nor anywhere within its `bounding_box`.
+    /// Usually this means the container is scaled too small, or the spheres you're
+    /// attempting to pack are too large. Meshes can be rescaled with
+    /// [normalize_trimesh](../util/fn.normalize_trimesh.html).
     Uncontained,
     /// We choose a random value from the `set_f` vector. `rand` returns an option and we pop
     /// the value. If it's `None` this error is thrown. Due to the contstuction of the
@@ -24,6 +59,9 @@ pub enum SphericalCowError {
     /// the value. If it's `None` this error is thrown. Due to the contstuction of the
     /// rest of the method, it's safe to say this is unreachable.
     NoneFront,
+    /// A saved front handed to a resumed packing refers to a sphere which is not part of that packing,
+    /// or to the same sphere more than once.
+    InvalidFront,
 }
 
 impl fmt::Display for SphericalCowError {
@@ -31,6 +69,39 @@ impl fmt::Display for SphericalCowError {
         match *self {
             SphericalCowError::NegativeRadius => write!(f, "Supplied radius is negative."),
             SphericalCowError::NegativeExtents => write!(f, "A supplied half extent is negative."),
+            SphericalCowError::ExtentCount => {
+                write!(f, "Exactly three half extents must be supplied.")
+            }
+            SphericalCowError::NegativeHeight => write!(f, "Supplied height is negative."),
+            SphericalCowError::InvertedShell => write!(
+                f,
+                "Inner radius of a shell is not smaller than its outer radius."
+            ),
+            SphericalCowError::Unbounded => {
+                write!(f, "Container does not report a bounding box.")
+            }
+            SphericalCowError::NegativeScale => write!(f, "Supplied scale is not positive."),
+            SphericalCowError::NoSamples => {
+                write!(f, "Volume integrator takes no samples.")
+            }
+            SphericalCowError::TooManyCells => {
+                write!(f, "Volume integrator grid has too many cells to count.")
+            }
+            SphericalCowError::InvalidFace => {
+                write!(f, "Mesh face does not refer to three distinct vertices.")
+            }
+            SphericalCowError::OpenMesh => write!(f, "Mesh is not closed."),
+            SphericalCowError::InconsistentOrientation => {
+                write!(f, "Mesh faces are not consistently oriented.")
+            }
+            SphericalCowError::DegenerateMesh => {
+                write!(f, "Mesh has no volume or extent to scale.")
+            }
+            SphericalCowError::MeshRead => write!(f, "Unable to read mesh file."),
+            SphericalCowError::MalformedMesh => write!(f, "Mesh file is malformed."),
+            SphericalCowError::NonTriangularFace => {
+                write!(f, "Mesh has a face which is not a triangle.")
+            }
             SphericalCowError::Uncontained => {
                 write!(f, "Sphere is not contained within bounding geometry.")
             }
@@ -40,6 +111,12 @@ impl fmt::Display for SphericalCowError {
             SphericalCowError::NoneFront => {
                 write!(f, "Returned none when choosing value from front.")
             }
+            SphericalCowError::InvalidFront => {
+                write!(
+                    f,
+                    "Saved front refers to a sphere outside of the packing, or repeats one."
+                )
+            }
         }
     }
 }
@@ -66,6 +143,183 @@ fn error_display_negative_extent() {
     );
 }
 
+#[test]
+fn error_display_extent_count() {
+    use crate::shapes::PeriodicCuboid;
+
+    let err = PeriodicCuboid::from_vec(vec![1., 1.]).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Exactly three half extents must be supplied.")
+    );
+}
+
+#[test]
+fn error_display_negative_height() {
+    use crate::shapes::Cylinder;
+
+    let err = Cylinder::new(1., -1.).unwrap_err();
+    assert_eq!(format!("{}", err), format!("Supplied height is negative."));
+}
+
+#[test]
+fn error_display_inverted_shell() {
+    use crate::shapes::SphericalShell;
+    use nalgebra::Point3;
+
+    let err = SphericalShell::new(Point3::origin(), 2., 1.).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Inner radius of a shell is not smaller than its outer radius.")
+    );
+}
+
+#[test]
+fn error_display_unbounded() {
+    use crate::shapes::{Cuboid, PeriodicCuboid, Union};
+
+    let periodic = PeriodicCuboid::new(1., 1., 1.).unwrap();
+    let cuboid = Cuboid::new(1., 1., 1.).unwrap();
+    let err = Union::new(periodic, cuboid).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Container does not report a bounding box.")
+    );
+}
+
+#[test]
+fn error_display_negative_scale() {
+    use crate::shapes::{Sphere, Transformed};
+    use nalgebra::{Isometry3, Point3};
+
+    let sphere = Sphere::new(Point3::origin(), 1.).unwrap();
+    let err = Transformed::new(sphere, Isometry3::identity(), 0.).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Supplied scale is not positive.")
+    );
+}
+
+#[test]
+fn error_display_no_samples() {
+    use crate::shapes::{Integrator, SdfContainer};
+    use nalgebra::Point3;
+
+    let sdf = |p: Point3<f32>| p.coords.norm() - 1.;
+    let (min, max) = (Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.));
+    let err = SdfContainer::new(sdf, min, max, Integrator::Grid { resolution: 0 }).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Volume integrator takes no samples.")
+    );
+}
+
+#[test]
+fn error_display_too_many_cells() {
+    use crate::shapes::{Integrator, SdfContainer};
+    use nalgebra::Point3;
+
+    let sdf = |p: Point3<f32>| p.coords.norm() - 1.;
+    let (min, max) = (Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.));
+    let grid = Integrator::Grid {
+        resolution: usize::MAX,
+    };
+    let err = SdfContainer::new(sdf, min, max, grid).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Volume integrator grid has too many cells to count.")
+    );
+}
+
+#[test]
+fn error_display_invalid_face() {
+    use crate::shapes::TriMesh;
+    use nalgebra::Point3;
+
+    let err = TriMesh::new(vec![Point3::origin()], vec![[0, 0, 1]]).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Mesh face does not refer to three distinct vertices.")
+    );
+}
+
+#[test]
+fn error_display_open_mesh() {
+    use crate::shapes::TriMesh;
+    use nalgebra::Point3;
+
+    let vertices = vec![
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+    ];
+    let err = TriMesh::new(vertices, vec![[0, 1, 2]]).unwrap_err();
+    assert_eq!(format!("{}", err), format!("Mesh is not closed."));
+}
+
+#[test]
+fn error_display_inconsistent_orientation() {
+    use crate::shapes::TriMesh;
+    use nalgebra::Point3;
+
+    let vertices = vec![
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(0., 0., 1.),
+    ];
+    // The last face winds the same way as the first about their shared edge
+    let faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 2, 3]];
+    let err = TriMesh::new(vertices, faces).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Mesh faces are not consistently oriented.")
+    );
+}
+
+#[test]
+#[cfg(feature = "mesh-io")]
+fn error_display_mesh_read() {
+    use crate::shapes::TriMesh;
+
+    let err = TriMesh::load_stl("does/not/exist.stl").unwrap_err();
+    assert_eq!(format!("{}", err), format!("Unable to read mesh file."));
+}
+
+#[test]
+#[cfg(feature = "mesh-io")]
+fn error_display_malformed_mesh() {
+    use crate::shapes::TriMesh;
+
+    let err = TriMesh::read_stl(&b"not a mesh"[..]).unwrap_err();
+    assert_eq!(format!("{}", err), format!("Mesh file is malformed."));
+}
+
+#[test]
+#[cfg(feature = "mesh-io")]
+fn error_display_non_triangular_face() {
+    use crate::shapes::TriMesh;
+
+    let square = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n";
+    let err = TriMesh::read_obj(square.as_bytes()).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Mesh has a face which is not a triangle.")
+    );
+}
+
+#[test]
+fn error_display_degenerate_mesh() {
+    use crate::util::{normalize_trimesh, MeshSize};
+    use nalgebra::Point3;
+
+    let err = normalize_trimesh(&[], &Point3::origin(), MeshSize::Extent(1.)).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Mesh has no volume or extent to scale.")
+    );
+}
+
 #[test]
 fn error_display_containment() {
     use crate::init_spheres;
@@ -74,7 +328,7 @@ fn error_display_containment() {
 
     let container = Sphere::new(Point3::origin(), 0.1).unwrap();
 
-    let err = init_spheres(&[10., 15., 20.], &container).unwrap_err();
+    let err = init_spheres(&[10., 15., 20.], &Point3::origin(), &container).unwrap_err();
     assert_eq!(
         format!("{}", err),
         format!("Sphere is not contained within bounding geometry.")
@@ -108,3 +362,24 @@ fn error_display_empty_values() {
         format!("Returned none when choosing value from front.")
     );
 }
+
+#[test]
+fn error_display_invalid_front() {
+    use crate::builder::PackingBuilder;
+    use crate::shapes::Sphere;
+    use crate::PackedVolume;
+    use nalgebra::Point3;
+    use rand::distributions::Uniform;
+
+    let container = Sphere::new(Point3::origin(), 2.).unwrap();
+    let spheres = vec![Sphere::new(Point3::origin(), 0.5).unwrap()];
+
+    let err = PackingBuilder::resume(PackedVolume::from_vec(spheres, container))
+        .front(vec![0, 3])
+        .pack(&mut Uniform::new(0.1, 0.2))
+        .unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        format!("Saved front refers to a sphere outside of the packing, or repeats one.")
+    );
+}
@@ -0,0 +1,238 @@
+//! A uniform spatial hash used to accelerate neighbourhood queries during packing.
+
+use crate::shapes::Sphere;
+use nalgebra::{Point3, Vector3};
+use std::collections::HashMap;
+
+/// Index of a sphere in the packed set, along with its center.
+type Entry = (usize, Point3<f32>);
+
+/// Buckets sphere indices into cubic cells of equal side length. Only cells that hold
+/// at least one sphere are allocated, so unbounded or sparsely filled containers
+/// do not pay for empty space.
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    /// Side length of each cubic cell.
+    cell_size: f32,
+    /// Indices (into the packed sphere set) and centers of every sphere whose center lies in a
+    /// given cell. Centers are kept so the grid can be rebuilt with larger cells.
+    cells: HashMap<(i32, i32, i32), Vec<Entry>>,
+    /// The largest radius inserted so far, which bounds how far a neighbour's center can be
+    /// from any surface we query against.
+    max_radius: f32,
+}
+
+impl SpatialGrid {
+    /// Creates an empty grid. A `cell_size` of a few sphere diameters keeps queries
+    /// to the 27 cells surrounding the queried point.
+    pub fn new(cell_size: f32) -> SpatialGrid {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+            max_radius: 0.,
+        }
+    }
+
+    /// Builds a grid over all `spheres`, indexed by their position in the slice.
+    pub fn from_spheres(spheres: &[Sphere], cell_size: f32) -> SpatialGrid {
+        let mut grid = SpatialGrid::new(cell_size);
+        for (idx, sphere) in spheres.iter().enumerate() {
+            grid.insert(idx, sphere);
+        }
+        grid
+    }
+
+    /// The cell coordinates a point falls into.
+    fn cell(&self, point: &Point3<f32>) -> (i32, i32, i32) {
+        (
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+            (point.z / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Registers `sphere`, located at index `idx` of the packed set. Should the sphere be wider
+    /// than a cell, the grid is rebuilt with cells of a few of its diameters: queries reach at
+    /// least as far as the largest radius, so would otherwise visit many empty cells.
+    pub fn insert(&mut self, idx: usize, sphere: &Sphere) {
+        let key = self.cell(&sphere.center);
+        self.cells
+            .entry(key)
+            .or_default()
+            .push((idx, sphere.center));
+        if sphere.radius > self.max_radius {
+            self.max_radius = sphere.radius;
+            if 2. * self.max_radius > self.cell_size {
+                self.rehash((4. * self.max_radius).max(2. * self.cell_size));
+            }
+        }
+    }
+
+    /// Redistributes every sphere into cells with side length `cell_size`.
+    fn rehash(&mut self, cell_size: f32) {
+        self.cell_size = cell_size;
+        let entries: Vec<_> = self.cells.drain().flat_map(|(_, cell)| cell).collect();
+        for (idx, center) in entries {
+            let key = self.cell(&center);
+            self.cells.entry(key).or_default().push((idx, center));
+        }
+    }
+
+    /// Fills `found` with the indices of all spheres that may have a surface within `reach`
+    /// of `center`. This is a superset of the true neighbourhood: callers should
+    /// still apply their exact distance test. Indices are returned in ascending order
+    /// so results do not depend on cell layout.
+    pub fn neighbours(&self, center: &Point3<f32>, reach: f32, found: &mut Vec<usize>) {
+        found.clear();
+        let reach = reach + self.max_radius;
+        let (lo_x, lo_y, lo_z) = self.cell(&(center - Vector3::repeat(reach)));
+        let (hi_x, hi_y, hi_z) = self.cell(&(center + Vector3::repeat(reach)));
+        for x in lo_x..=hi_x {
+            for y in lo_y..=hi_y {
+                for z in lo_z..=hi_z {
+                    if let Some(cell) = self.cells.get(&(x, y, z)) {
+                        found.extend(cell.iter().map(|&(idx, _)| idx));
+                    }
+                }
+            }
+        }
+        found.sort_unstable();
+    }
+
+    /// As with [neighbours](#method.neighbours), but for spheres in a periodic cell centered at
+    /// the origin with edge lengths `period`. Neighbours across the cell's faces are found by also
+    /// querying the images of `center` on the far side of each face it lies near.
+    pub fn periodic_neighbours(
+        &self,
+        center: &Point3<f32>,
+        reach: f32,
+        period: &Vector3<f32>,
+        found: &mut Vec<usize>,
+    ) {
+        let reach_all = reach + self.max_radius;
+        // Per axis, the whole periods we need to shift the query by
+        let shifts = |axis: usize| -> Vec<f32> {
+            let mut shifts = vec![0.];
+            if center[axis] + reach_all > 0.5 * period[axis] {
+                shifts.push(-period[axis]);
+            }
+            if center[axis] - reach_all < -0.5 * period[axis] {
+                shifts.push(period[axis]);
+            }
+            shifts
+        };
+        let mut image_found = Vec::new();
+        found.clear();
+        for &x in shifts(0).iter() {
+            for &y in shifts(1).iter() {
+                for &z in shifts(2).iter() {
+                    let image = center + Vector3::new(x, y, z);
+                    self.neighbours(&image, reach, &mut image_found);
+                    found.extend_from_slice(&image_found);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+    }
+}
+
+#[cfg(test)]
+/// Indices of every sphere whose surface lies within `reach` of `center`, found by scanning them all.
+fn brute_force(spheres: &[Sphere], center: &Point3<f32>, reach: f32) -> Vec<usize> {
+    (0..spheres.len())
+        .filter(|&idx| {
+            nalgebra::distance(center, &spheres[idx].center) <= reach + spheres[idx].radius
+        })
+        .collect()
+}
+
+#[test]
+fn neighbours_across_cell_boundaries() {
+    let spheres = [
+        // Exactly on a boundary, so in cell (1, 0, 0)
+        Sphere::new(Point3::new(1., 0.5, 0.5), 0.1).unwrap(),
+        // Just below zero, so in cell (-1, 0, 0) rather than truncated into (0, 0, 0)
+        Sphere::new(Point3::new(-0.01, 0.5, 0.5), 0.1).unwrap(),
+        Sphere::new(Point3::new(3.5, 0.5, 0.5), 0.1).unwrap(),
+    ];
+    let grid = SpatialGrid::from_spheres(&spheres, 1.);
+    let mut found = Vec::new();
+
+    grid.neighbours(&Point3::new(0.95, 0.5, 0.5), 0., &mut found);
+    assert!(found.contains(&0));
+    grid.neighbours(&Point3::new(0.05, 0.5, 0.5), 0., &mut found);
+    assert!(found.contains(&1));
+    assert!(!found.contains(&2));
+    grid.neighbours(&Point3::new(0.5, 0.5, 0.5), 2.9, &mut found);
+    assert_eq!(found, vec![0, 1, 2]);
+}
+
+#[test]
+fn neighbours_match_brute_force() {
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let spheres: Vec<Sphere> = (0..500)
+        .map(|_| {
+            let center = Point3::new(
+                rng.gen_range(-5.0..5.0),
+                rng.gen_range(-5.0..5.0),
+                rng.gen_range(-5.0..5.0),
+            );
+            Sphere::new(center, rng.gen_range(0.1..0.3)).unwrap()
+        })
+        .collect();
+    let grid = SpatialGrid::from_spheres(&spheres, 0.8);
+    let mut found = Vec::new();
+    for sphere in spheres.iter() {
+        let reach = sphere.radius + 0.4;
+        grid.neighbours(&sphere.center, reach, &mut found);
+        let exact = brute_force(&spheres, &sphere.center, reach);
+        assert!(exact.iter().all(|idx| found.contains(idx)));
+    }
+}
+
+#[test]
+fn periodic_neighbours_wrap() {
+    let period = Vector3::new(4., 4., 4.);
+    let spheres = [
+        // Across the x face from the query
+        Sphere::new(Point3::new(1.95, 0., 0.), 0.1).unwrap(),
+        // Across the corner diagonally opposite the query
+        Sphere::new(Point3::new(1.95, 1.95, 1.95), 0.1).unwrap(),
+        Sphere::new(Point3::new(0., 0., 0.), 0.1).unwrap(),
+    ];
+    let grid = SpatialGrid::from_spheres(&spheres, 1.);
+    let mut found = Vec::new();
+
+    let center = Point3::new(-1.95, -1.95, -1.95);
+    grid.neighbours(&center, 0.1, &mut found);
+    assert!(found.is_empty());
+    grid.periodic_neighbours(&center, 0.1, &period, &mut found);
+    assert_eq!(found, vec![1]);
+    grid.periodic_neighbours(&Point3::new(-1.95, 0., 0.), 0.1, &period, &mut found);
+    assert_eq!(found, vec![0]);
+    // Spheres found through several images are only reported once
+    grid.periodic_neighbours(&Point3::new(1.95, 1.95, 1.95), 3.9, &period, &mut found);
+    assert_eq!(found, vec![0, 1, 2]);
+}
+
+#[test]
+fn grid_grows_with_large_spheres() {
+    let spheres = [
+        Sphere::new(Point3::new(0.05, 0.05, 0.05), 0.01).unwrap(),
+        Sphere::new(Point3::new(-0.3, 0.2, 0.), 0.01).unwrap(),
+    ];
+    let mut grid = SpatialGrid::from_spheres(&spheres, 0.04);
+    let large = Sphere::new(Point3::new(3., 0., 0.), 1.).unwrap();
+    grid.insert(2, &large);
+
+    // Cells are now several diameters of the largest sphere, and nothing was lost in moving
+    assert!(grid.cell_size >= 4.);
+    let mut found = Vec::new();
+    grid.neighbours(&Point3::origin(), 2., &mut found);
+    assert_eq!(found, vec![0, 1, 2]);
+    grid.neighbours(&Point3::new(-0.3, 0.2, 0.), 0., &mut found);
+    assert!(found.contains(&1));
+}
@@ -6,15 +6,20 @@
 //!
 //! ```toml
 //! [dependencies]
-//! spherical-cow = "0.1"
+//! spherical-cow = "0.2"
 //! ```
 //!
 //! If you'd like to enable serialization through `serde` add this line instead to turn on that feature
 //!
 //! ```toml
-//! spherical-cow = { version = "0.1", features = ["serde-1"] }
+//! spherical-cow = { version = "0.2", features = ["serde-1"] }
 //! ```
 //!
+//! Similarly, the `rayon` feature enables multi-threaded packing via
+//! [PackingBuilder::pack_parallel](builder/struct.PackingBuilder.html#method.pack_parallel),
+//! and the `mesh-io` feature loads [TriMesh](shapes/struct.TriMesh.html) containers from
+//! Wavefront OBJ and STL files.
+//!
 //! To calculate the `volume_fraction` of a spherical container with radius 2 filled with spheres of radii between 0.05 and 0.1 is straightforward:
 //!
 //! ```rust,no_run
@@ -44,21 +49,38 @@
 
 #![warn(missing_docs)]
 #![allow(clippy::needless_doctest_main)]
+// `Option::is_some_and` would raise the minimum supported compiler to 1.70
+#![allow(clippy::unnecessary_map_or)]
 
+pub mod builder;
 pub mod errors;
+mod grid;
+#[cfg(feature = "mesh-io")]
+mod mesh_io;
+#[cfg(feature = "rayon")]
+mod parallel;
 #[cfg(feature = "serde-1")]
 mod serialization;
 pub mod shapes;
 pub mod util;
 
+/// Only exposed so the benchmarks can compare it with a brute force search; not part of the
+/// public API.
+#[doc(hidden)]
+pub use crate::grid::SpatialGrid;
+
+use crate::builder::{Control, Limits, PackingObserver, Progress, Termination};
 use crate::errors::SphericalCowError as Error;
 use crate::shapes::Sphere;
 use float_cmp::ApproxEqRatio;
 use itertools::Itertools;
 use nalgebra::core::{Matrix, Matrix3};
-use nalgebra::Point3;
+use nalgebra::{Point3, Vector3};
 use rand::distributions::Distribution;
-use rand::prelude::SliceRandom;
+use rand::prelude::{IteratorRandom, SliceRandom};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Instant;
 
 /// The `Container` trait must be implemented for all shapes you wish to pack spheres into.
 /// Standard shapes such as spheres and cuboids already derrive this trait. More complicated
@@ -71,6 +93,37 @@ pub trait Container {
     fn contains(&self, sphere: &Sphere) -> bool;
     /// Calculates the volume of this container in normalised units.
     fn volume(&self) -> f32;
+    /// If this container tiles space periodically, the edge lengths of one periodic cell,
+    /// which must be centered at the origin. Spheres are then free to cross the container's faces
+    /// and are tested for overlaps against the periodic images of their neighbours.
+    /// Defaults to `None`: a closed container.
+    fn period(&self) -> Option<Vector3<f32>> {
+        None
+    }
+    /// A point well inside the container, about which the three initial spheres of a
+    /// packing are placed. Defaults to the origin.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::origin()
+    }
+    /// The minimum and maximum corners of an axis aligned box enclosing the container.
+    /// If the initial spheres do not fit about the [seed_point](#method.seed_point), packing
+    /// searches this box for another seed. Defaults to `None`: no search is attempted.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        None
+    }
+    /// This container as a `Sphere`, if that is what it is. Combinations of spheres then find
+    /// the volume they share exactly rather than estimating it. Defaults to `None`.
+    fn as_sphere(&self) -> Option<Sphere> {
+        None
+    }
+}
+
+/// Shapes which can tell when a sphere lies entirely outside of them. This is needed of any
+/// shape cut away from another by a [Difference](shapes/struct.Difference.html).
+pub trait Disjoint {
+    /// Checks if a sphere shares no volume with this shape. Spheres touching the surface
+    /// from outside are disjoint.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool;
 }
 
 /// To obtain quantitative values of your packing effectiveness, `PackedVolume` provides
@@ -88,15 +141,38 @@ impl<C: Container> PackedVolume<C> {
     /// and a `container` to pack into.
     pub fn new<D: Distribution<f64>>(
         container: C,
-        mut size_distribution: &mut D,
+        size_distribution: &mut D,
     ) -> Result<PackedVolume<C>, Error> {
-        let spheres = pack_spheres::<C, D>(&container, &mut size_distribution)?;
+        let spheres = pack_spheres::<C, D>(&container, size_distribution)?;
         Ok(PackedVolume::<C> { spheres, container })
     }
 
+    /// Identical to [new](#method.new), but all random choices made during packing are drawn from
+    /// the supplied `rng` rather than `rand::thread_rng()`.
+    pub fn new_with_rng<D: Distribution<f64>, R: Rng + ?Sized>(
+        container: C,
+        size_distribution: &mut D,
+        rng: &mut R,
+    ) -> Result<PackedVolume<C>, Error> {
+        let spheres = pack_spheres_with_rng::<C, D, R>(&container, size_distribution, rng)?;
+        Ok(PackedVolume::<C> { spheres, container })
+    }
+
+    /// Creates a reproducible `PackedVolume`: packing the same `container` with the same
+    /// `size_distribution` and `seed` will always yield an identical set of spheres.
+    pub fn new_seeded<D: Distribution<f64>>(
+        container: C,
+        size_distribution: &mut D,
+        seed: u64,
+    ) -> Result<PackedVolume<C>, Error> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        PackedVolume::new_with_rng(container, size_distribution, &mut rng)
+    }
+
     /// Creates a `PackedVolume` from a pre calculated cluster of `spheres`. Useful for gathering statistics from
     /// packings generated elsewhere for comparison to the current algorithm. Also used for deserialization.
-    /// This method is currently unchecked, so use with caution.
+    /// This method is currently unchecked, so use with caution. To continue packing on top of
+    /// these spheres, see [PackingBuilder::resume](builder/struct.PackingBuilder.html#method.resume).
     pub fn from_vec(spheres: Vec<Sphere>, container: C) -> PackedVolume<C> {
         PackedVolume::<C> { spheres, container }
     }
@@ -165,13 +241,15 @@ impl<C: Container> PackedVolume<C> {
         Matrix3::from_fn(phi)
     }
 
-    /// Returns a set of spheres connected to the sphere at a chosen index.
+    /// Returns a set of spheres connected to the sphere at a chosen index. In periodic
+    /// containers, these are the images of each contact closest to the chosen sphere.
     fn sphere_contacts(&self, sphere_idx: usize) -> Vec<Sphere> {
         let center = self.spheres[sphere_idx].center;
         let radius = self.spheres[sphere_idx].radius;
+        let period = self.container.period();
         self.spheres
             .iter()
-            .cloned()
+            .map(|sphere| nearest_image(&center, sphere, period))
             .filter(|sphere| {
                 nalgebra::distance(&center, &sphere.center)
                     .approx_eq_ratio(&(radius + sphere.radius), 0.0001)
@@ -183,10 +261,11 @@ impl<C: Container> PackedVolume<C> {
     fn sphere_contacts_count(&self, sphere_idx: usize) -> usize {
         let center = self.spheres[sphere_idx].center;
         let radius = self.spheres[sphere_idx].radius;
+        let period = self.container.period();
         self.spheres
             .iter()
             .filter(|sphere| {
-                nalgebra::distance(&center, &sphere.center)
+                Matrix::norm(&separation(&center, &sphere.center, period))
                     .approx_eq_ratio(&(radius + sphere.radius), 0.0001)
             })
             .count()
@@ -205,76 +284,357 @@ pub fn pack_spheres<C: Container, D: Distribution<f64>>(
     container: &C,
     size_distribution: &mut D,
 ) -> Result<Vec<Sphere>, Error> {
+    pack_spheres_with_rng(container, size_distribution, &mut rand::thread_rng())
+}
+
+/// Identical to [pack_spheres](fn.pack_spheres.html), although randomness is sourced from
+/// a caller supplied `rng`. Using a seeded generator such as `rand::rngs::StdRng` makes a
+/// packing reproducible bit-for-bit, which is handy for regression tests and bug reports.
+pub fn pack_spheres_with_rng<C: Container, D: Distribution<f64>, R: Rng + ?Sized>(
+    container: &C,
+    size_distribution: &mut D,
+    rng: &mut R,
+) -> Result<Vec<Sphere>, Error> {
+    let (mut spheres, mut front) = init_front(container, size_distribution, rng)?;
+    advance_front(
+        container,
+        size_distribution,
+        rng,
+        &mut spheres,
+        &mut front,
+        &Limits::default(),
+        None,
+    )?;
+    Ok(spheres)
+}
+
+/// Builds the initial front from three spheres with radii drawn from `size_distribution`.
+/// Returns the packed set S and the front F, which holds indices into S.
+pub(crate) fn init_front<C: Container, D: Distribution<f64>, R: Rng + ?Sized>(
+    container: &C,
+    size_distribution: &mut D,
+    rng: &mut R,
+) -> Result<(Vec<Sphere>, Vec<usize>), Error> {
     // Distribution is already derrived for all distributions in `rand` with f64,
     // so we just downsample here instead of implementing traits on f32 for everything.
-    let mut rng = rand::thread_rng();
-
     // Radii of three initial spheres, taken from the input distribution
     let init_radii: [f32; 3] = [
-        size_distribution.sample(&mut rng) as f32,
-        size_distribution.sample(&mut rng) as f32,
-        size_distribution.sample(&mut rng) as f32,
+        size_distribution.sample(rng) as f32,
+        size_distribution.sample(rng) as f32,
+        size_distribution.sample(rng) as f32,
     ];
 
     // S := {s₁, s₂, s₃}
-    let mut spheres = init_spheres(&init_radii, container)?;
+    let spheres = match init_spheres(&init_radii, &container.seed_point(), container) {
+        Err(Error::Uncontained) => seek_init_spheres(&init_radii, container, rng)?,
+        result => result?,
+    };
+
+    // F := {s₁, s₂, s₃}, stored as indices into S so that exhausted spheres can be
+    // dropped with a swap rather than searched for and shifted out.
+    let front: Vec<usize> = (0..spheres.len()).collect();
 
-    // F := {s₁, s₂, s₃}
-    let mut front = spheres.clone();
+    Ok((spheres, front))
+}
+
+/// Grows `spheres` from the current `front` until either the front is exhausted or
+/// one of the supplied `limits` is reached, reporting which of these ended the run.
+/// If an `observer` is supplied, it is notified of every change to the front and may
+/// cancel the run.
+pub(crate) fn advance_front<C: Container, D: Distribution<f64>, R: Rng + ?Sized>(
+    container: &C,
+    size_distribution: &mut D,
+    rng: &mut R,
+    spheres: &mut Vec<Sphere>,
+    front: &mut Vec<usize>,
+    limits: &Limits,
+    observer: Option<&mut dyn PackingObserver>,
+) -> Result<Termination, Error> {
+    let mut grid = front_grid(spheres);
+    let mut run = Run::new(container, spheres, limits, observer);
 
     // Radius of new sphere to be added to the current front, taken from the input distribution
-    let mut new_radius = size_distribution.sample(&mut rng) as f32;
+    let mut new_radius = size_distribution.sample(rng) as f32;
 
+    let mut neighbours = Vec::new();
     let mut set_v = Vec::new();
     let mut set_f = Vec::new();
-    'outer: while !front.is_empty() {
+    loop {
+        if let Some(termination) = run.limit_reached(spheres.len(), front.len()) {
+            return Ok(termination);
+        }
+        run.iterations += 1;
+
         // s₀ := s(c₀, r₀) picked at random from F
-        let curr_sphere = front.choose(&mut rng).ok_or(Error::NoneFront)?.clone();
-        // V := {s(c', r') ∈ S : d(c₀, c') ≤ r₀ + r' + 2r}
-        set_v.clear();
-        set_v = spheres
+        let front_pos = (0..front.len()).choose(rng).ok_or(Error::NoneFront)?;
+        find_placements(
+            container,
+            spheres,
+            &grid,
+            front[front_pos],
+            new_radius,
+            &mut neighbours,
+            &mut set_v,
+            &mut set_f,
+        )?;
+        let event = if !set_f.is_empty() {
+            // Found at least one position to place the sphere,
+            // choose one and move on
+            let s_new = set_f.choose(rng).ok_or(Error::NoneSetF)?;
+            front.push(spheres.len());
+            grid.insert(spheres.len(), s_new);
+            spheres.push(s_new.clone());
+            new_radius = size_distribution.sample(rng) as f32;
+            run.placed(s_new, spheres.len(), front.len())
+        } else {
+            // No room is left around s₀, so it leaves the front
+            front.swap_remove(front_pos);
+            run.exhausted(spheres.len(), front.len())
+        };
+        if let Some(termination) = event {
+            return Ok(termination);
+        }
+    }
+}
+
+/// Neighbourhood index over S. Cells span a few sphere diameters so that
+/// each query only needs to visit the cells directly surrounding s₀, and grow
+/// should larger spheres be placed later on.
+pub(crate) fn front_grid(spheres: &[Sphere]) -> SpatialGrid {
+    let cell_size = 4. * spheres.iter().map(|s| s.radius).sum::<f32>() / spheres.len() as f32;
+    SpatialGrid::from_spheres(spheres, cell_size)
+}
+
+/// Bookkeeping for a single run of the advancing front: tracks progress towards each
+/// termination criterion and keeps any observer informed.
+pub(crate) struct Run<'l, 'o> {
+    limits: &'l Limits,
+    observer: Option<&'o mut dyn PackingObserver>,
+    container_volume: f32,
+    packed_volume: f32,
+    started: Instant,
+    /// Number of front spheres visited so far.
+    pub(crate) iterations: usize,
+}
+
+impl<'l, 'o> Run<'l, 'o> {
+    pub(crate) fn new<C: Container>(
+        container: &C,
+        spheres: &[Sphere],
+        limits: &'l Limits,
+        observer: Option<&'o mut dyn PackingObserver>,
+    ) -> Run<'l, 'o> {
+        // Only bother finding the container volume if we're asked to stop at, or report, some fraction of it
+        let container_volume = if limits.volume_fraction.is_some() || observer.is_some() {
+            container.volume()
+        } else {
+            0.
+        };
+        Run {
+            limits,
+            observer,
+            container_volume,
+            packed_volume: spheres.iter().map(|s| s.volume()).sum(),
+            started: Instant::now(),
+            iterations: 0,
+        }
+    }
+
+    /// Checks whether the packing has grown to its requested size.
+    pub(crate) fn target_reached(&self, spheres: usize) -> Option<Termination> {
+        if self.limits.max_spheres.map_or(false, |max| spheres >= max) {
+            Some(Termination::SphereCount)
+        } else if self.limits.volume_fraction.map_or(false, |fraction| {
+            self.packed_volume >= fraction * self.container_volume
+        }) {
+            Some(Termination::VolumeFraction)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the run should end before visiting another front sphere.
+    pub(crate) fn limit_reached(&self, spheres: usize, front: usize) -> Option<Termination> {
+        if let Some(termination) = self.target_reached(spheres) {
+            Some(termination)
+        } else if front == 0 {
+            Some(Termination::FrontExhausted)
+        } else if self
+            .limits
+            .max_iterations
+            .map_or(false, |max| self.iterations >= max)
+        {
+            Some(Termination::Iterations)
+        } else if self
+            .limits
+            .time_budget
+            .map_or(false, |budget| self.started.elapsed() >= budget)
+        {
+            Some(Termination::TimeBudget)
+        } else {
+            None
+        }
+    }
+
+    /// Records that `sphere` has been added to the packing.
+    pub(crate) fn placed(
+        &mut self,
+        sphere: &Sphere,
+        spheres: usize,
+        front: usize,
+    ) -> Option<Termination> {
+        self.packed_volume += sphere.volume();
+        let progress = self.progress(spheres, front);
+        match self.observer.as_mut() {
+            Some(observer) => match observer.sphere_placed(&progress) {
+                Control::Cancel => Some(Termination::Cancelled),
+                Control::Continue => None,
+            },
+            None => None,
+        }
+    }
+
+    /// Records that a sphere has left the front.
+    pub(crate) fn exhausted(&mut self, spheres: usize, front: usize) -> Option<Termination> {
+        let progress = self.progress(spheres, front);
+        match self.observer.as_mut() {
+            Some(observer) => match observer.sphere_exhausted(&progress) {
+                Control::Cancel => Some(Termination::Cancelled),
+                Control::Continue => None,
+            },
+            None => None,
+        }
+    }
+
+    fn progress(&self, spheres: usize, front: usize) -> Progress {
+        Progress {
+            spheres,
+            front,
+            volume_fraction: self.packed_volume / self.container_volume,
+            iterations: self.iterations,
+        }
+    }
+}
+
+/// Searches the neighbourhood of s₀ = `spheres[curr_idx]` for positions in which a sphere
+/// of `radius` can be placed. On return, `set_f` holds the (at most two) positions found
+/// around the first suitable pair of neighbours, or is empty if s₀ has no room left.
+/// `neighbours` and `set_v` are scratch space, passed in to avoid reallocating them on
+/// every iteration.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn find_placements<C: Container>(
+    container: &C,
+    spheres: &[Sphere],
+    grid: &SpatialGrid,
+    curr_idx: usize,
+    radius: f32,
+    neighbours: &mut Vec<usize>,
+    set_v: &mut Vec<Sphere>,
+    set_f: &mut Vec<Sphere>,
+) -> Result<(), Error> {
+    let curr_sphere = &spheres[curr_idx];
+    let period = container.period();
+    // V := {s(c', r') ∈ S : d(c₀, c') ≤ r₀ + r' + 2r}
+    // For periodic containers, V holds the images of each neighbour closest to s₀.
+    let reach = curr_sphere.radius + 2. * radius;
+    match period {
+        Some(period) => grid.periodic_neighbours(&curr_sphere.center, reach, &period, neighbours),
+        None => grid.neighbours(&curr_sphere.center, reach, neighbours),
+    }
+    set_v.clear();
+    set_v.extend(
+        neighbours
             .iter()
-            .cloned()
+            .filter(|&&idx| idx != curr_idx)
+            .map(|&idx| nearest_image(&curr_sphere.center, &spheres[idx], period))
             .filter(|s_dash| {
-                s_dash != &curr_sphere
-                    && nalgebra::distance(&curr_sphere.center, &s_dash.center)
-                        <= curr_sphere.radius + s_dash.radius + 2. * new_radius
-            })
-            .collect::<Vec<_>>();
-
-        for (s_i, s_j) in set_v.iter().tuple_combinations::<(&Sphere, &Sphere)>() {
-            set_f.clear();
-            identify_f(
-                &mut set_f,
-                &curr_sphere,
-                s_i,
-                s_j,
-                container,
-                &set_v,
-                new_radius,
-            )?;
-            if !set_f.is_empty() {
-                // Found at least one position to place the sphere,
-                // choose one and move on
-                let s_new = set_f.choose(&mut rng).ok_or(Error::NoneSetF)?;
-                front.push(s_new.clone());
-                spheres.push(s_new.clone());
-                new_radius = size_distribution.sample(&mut rng) as f32;
-                continue 'outer;
-            }
+                nalgebra::distance(&curr_sphere.center, &s_dash.center)
+                    <= curr_sphere.radius + s_dash.radius + 2. * radius
+            }),
+    );
+
+    set_f.clear();
+    for (s_i, s_j) in set_v.iter().tuple_combinations::<(&Sphere, &Sphere)>() {
+        identify_f(set_f, curr_sphere, s_i, s_j, container, set_v, radius)?;
+        if !set_f.is_empty() {
+            break;
         }
-        if let Some(i) = front.iter().position(|s| s == &curr_sphere) {
-            front.remove(i);
+    }
+    Ok(())
+}
+
+/// The shortest vector from `from` to `to`. If a `period` is given, this is the vector
+/// to the closest periodic image of `to` (the minimum image convention).
+pub(crate) fn separation(
+    from: &Point3<f32>,
+    to: &Point3<f32>,
+    period: Option<Vector3<f32>>,
+) -> Vector3<f32> {
+    let delta = to - from;
+    match period {
+        Some(period) => delta.zip_map(&period, |d, p| d - p * (d / p).round()),
+        None => delta,
+    }
+}
+
+/// The periodic image of `sphere` closest to `point`, or simply `sphere` if there is no `period`.
+pub(crate) fn nearest_image(
+    point: &Point3<f32>,
+    sphere: &Sphere,
+    period: Option<Vector3<f32>>,
+) -> Sphere {
+    match period {
+        Some(_) => Sphere {
+            center: point + separation(point, &sphere.center, period),
+            radius: sphere.radius,
+        },
+        None => sphere.clone(),
+    }
+}
+
+/// Moves `sphere` by whole periods of a periodic `container` such that its center lies in
+/// the primary cell. Spheres in closed containers are left where they are.
+fn wrap<C: Container>(container: &C, sphere: &Sphere) -> Sphere {
+    match container.period() {
+        Some(period) => Sphere {
+            center: Point3::origin() + separation(&Point3::origin(), &sphere.center, Some(period)),
+            radius: sphere.radius,
+        },
+        None => sphere.clone(),
+    }
+}
+
+/// Number of random seed points tried within a container's bounding box before giving up.
+const SEED_ATTEMPTS: usize = 1000;
+
+/// Searches the container's bounding box for a seed about which the three initial spheres fit.
+/// Used when the container's own `seed_point` is unsuitable.
+fn seek_init_spheres<C: Container, R: Rng + ?Sized>(
+    radii: &[f32; 3],
+    container: &C,
+    rng: &mut R,
+) -> Result<Vec<Sphere>, Error> {
+    let (min, max) = container.bounding_box().ok_or(Error::Uncontained)?;
+    for _ in 0..SEED_ATTEMPTS {
+        let seed = Point3::from(
+            min.coords
+                .zip_map(&max.coords, |lo, hi| lo + (hi - lo) * rng.gen::<f32>()),
+        );
+        match init_spheres(radii, &seed, container) {
+            Err(Error::Uncontained) => continue,
+            result => return result,
         }
     }
-    Ok(spheres)
+    Err(Error::Uncontained)
 }
 
 /// Creates three initial spheres that are tangent pairwise. The incenter of the triangle formed
-/// by verticies located at the centers of each sphere is aligned at the origin.
-fn init_spheres<C: Container>(radii: &[f32; 3], container: &C) -> Result<Vec<Sphere>, Error> {
-    let mut init = Vec::new();
-
+/// by verticies located at the centers of each sphere is aligned at `seed`.
+fn init_spheres<C: Container>(
+    radii: &[f32; 3],
+    seed: &Point3<f32>,
+    container: &C,
+) -> Result<Vec<Sphere>, Error> {
     //            C (x,y)
     //            ^
     //           / \
@@ -303,32 +663,31 @@ fn init_spheres<C: Container>(radii: &[f32; 3], container: &C) -> Result<Vec<Sph
     let incenter_y = (distance_c * y) / perimeter;
 
     // Create spheres at positions shown in the diagram above, but offset such
-    // that the incenter is now the origin. This offset attempts to minimise
+    // that the incenter is now the seed point. This offset attempts to minimise
     // bounding box issues in the sense that c may be close to or over the
     // bb boundary already
-    let s_1 = Sphere::new(Point3::new(-incenter_x, -incenter_y, 0.), radius_a)?;
-    if container.contains(&s_1) {
-        init.push(s_1);
-    } else {
-        return Err(Error::Uncontained);
-    }
-    let s_2 = Sphere::new(
-        Point3::new(distance_c - incenter_x, -incenter_y, 0.),
-        radius_b,
-    )?;
-    if container.contains(&s_2) {
-        init.push(s_2);
-    } else {
-        return Err(Error::Uncontained);
-    }
-    let s_3 = Sphere::new(Point3::new(x - incenter_x, y - incenter_y, 0.), radius_c)?;
-    if container.contains(&s_3) {
-        init.push(s_3);
-    } else {
-        return Err(Error::Uncontained);
+    let offsets = [
+        (-incenter_x, -incenter_y, radius_a),
+        (distance_c - incenter_x, -incenter_y, radius_b),
+        (x - incenter_x, y - incenter_y, radius_c),
+    ];
+    // The triangle is laid in the xy plane if it fits there. Otherwise the yz and xz planes are
+    // tried, which suits thin walled containers such as shells seeded on the x axis.
+    let planes: [fn(f32, f32) -> Vector3<f32>; 3] = [
+        |u, v| Vector3::new(u, v, 0.),
+        |u, v| Vector3::new(0., u, v),
+        |u, v| Vector3::new(u, 0., v),
+    ];
+    for plane in planes.iter() {
+        let init = offsets
+            .iter()
+            .map(|&(u, v, radius)| Sphere::new(seed + plane(u, v), radius))
+            .collect::<Result<Vec<Sphere>, Error>>()?;
+        if init.iter().all(|sphere| container.contains(sphere)) {
+            return Ok(init);
+        }
     }
-
-    Ok(init)
+    Err(Error::Uncontained)
 }
 
 /// $f$ is as a set of spheres (or the empty set) such that they have a known `radius`,
@@ -367,27 +726,36 @@ fn identify_f<C: Container>(
     let distance_24 = s_2.radius + radius;
     let distance_34 = s_3.radius + radius;
 
-    let vector_u = s_1.center - s_2.center;
+    // Solve about the whole numbered point between s_1 and the origin: working close to the origin keeps the
+    // squared coordinates below from swamping the (much smaller) distances in f32, which would
+    // otherwise leave solutions far from the origin short of tangency. Shifting by whole numbers
+    // is exact, so spheres within a unit of the origin are solved just as they were before.
+    let origin = s_1.center.map(f32::trunc);
+    let center_1 = s_1.center - origin.coords;
+    let center_2 = s_2.center - origin.coords;
+    let center_3 = s_3.center - origin.coords;
+
+    let vector_u = center_1 - center_2;
     let unitvector_u = vector_u / Matrix::norm(&vector_u);
-    let vector_v = s_1.center - s_3.center;
+    let vector_v = center_1 - center_3;
     let unitvector_v = vector_v / Matrix::norm(&vector_v);
     let cross_uv = Matrix::cross(&vector_u, &vector_v);
     let unitvector_t = cross_uv / Matrix::norm(&cross_uv);
-    let vector_w = -2. * s_1.center.coords;
+    let vector_w = -2. * center_1.coords;
 
     let distance_c =
-        distance_14.powi(2) - s_1.center.x.powi(2) - s_1.center.y.powi(2) - s_1.center.z.powi(2);
+        distance_14.powi(2) - center_1.x.powi(2) - center_1.y.powi(2) - center_1.z.powi(2);
     let distance_a = (distance_24.powi(2)
         - distance_c
-        - s_2.center.x.powi(2)
-        - s_2.center.y.powi(2)
-        - s_2.center.z.powi(2))
+        - center_2.x.powi(2)
+        - center_2.y.powi(2)
+        - center_2.z.powi(2))
         / (2. * Matrix::norm(&vector_u));
     let distance_b = (distance_34.powi(2)
         - distance_c
-        - s_3.center.x.powi(2)
-        - s_3.center.y.powi(2)
-        - s_3.center.z.powi(2))
+        - center_3.x.powi(2)
+        - center_3.y.powi(2)
+        - center_3.z.powi(2))
         / (2. * Matrix::norm(&vector_v));
 
     let dot_uv = Matrix::dot(&unitvector_u, &unitvector_v);
@@ -412,20 +780,32 @@ fn identify_f<C: Container>(
         let gamma_neg = 0.5 * (-dot_wt - (dot_wt_2 - value_4d).sqrt());
 
         let s_4_positive = Sphere::new(
-            Point3::from(alpha * unitvector_u + beta * unitvector_v + gamma_pos * unitvector_t),
+            origin + alpha * unitvector_u + beta * unitvector_v + gamma_pos * unitvector_t,
             radius,
         )?;
         let s_4_negative = Sphere::new(
-            Point3::from(alpha * unitvector_u + beta * unitvector_v + gamma_neg * unitvector_t),
+            origin + alpha * unitvector_u + beta * unitvector_v + gamma_neg * unitvector_t,
             radius,
         )?;
 
-        // Make sure the spheres are bounded by the containing geometry and do not overlap any spheres in V
-        if container.contains(&s_4_positive) && !set_v.iter().any(|v| v.overlaps(&s_4_positive)) {
-            set_f.push(s_4_positive);
+        // Make sure the spheres are bounded by the containing geometry and do not overlap any spheres in V.
+        // s_2 and s_3 are tangent to both solutions by construction, so are skipped: rounding
+        // could otherwise report a spurious overlap with them. They are borrowed from V itself, so
+        // are told apart by address rather than by value.
+        // Periodic containers only see (and keep) the position wrapped back into their primary cell.
+        let overlaps_v = |s_4: &Sphere| {
+            set_v
+                .iter()
+                .filter(|&v| !std::ptr::eq(v, s_2) && !std::ptr::eq(v, s_3))
+                .any(|v| v.overlaps(s_4))
+        };
+        let wrapped_positive = wrap(container, &s_4_positive);
+        if container.contains(&wrapped_positive) && !overlaps_v(&s_4_positive) {
+            set_f.push(wrapped_positive);
         }
-        if container.contains(&s_4_negative) && !set_v.iter().any(|v| v.overlaps(&s_4_negative)) {
-            set_f.push(s_4_negative);
+        let wrapped_negative = wrap(container, &s_4_negative);
+        if container.contains(&wrapped_negative) && !overlaps_v(&s_4_negative) {
+            set_f.push(wrapped_negative);
         }
     }
     Ok(())
@@ -434,7 +814,19 @@ fn identify_f<C: Container>(
 #[test]
 fn init_spheres_err() {
     let container = Sphere::new(Point3::origin(), 0.1).unwrap();
-    assert!(init_spheres(&[10., 15., 20.], &container).is_err());
+    assert!(init_spheres(&[10., 15., 20.], &Point3::origin(), &container).is_err());
+}
+
+#[test]
+fn init_spheres_turns_to_fit() {
+    use crate::shapes::CylindricalShell;
+
+    // Too thin a wall for the triangle to lie flat in xy about the seed
+    let container = CylindricalShell::new(1., 1.5, 4.).unwrap();
+    let init = init_spheres(&[0.2, 0.2, 0.2], &container.seed_point(), &container).unwrap();
+    assert!(init
+        .iter()
+        .all(|s| s.center.x == 1.25 && container.contains(s)));
 }
 
 #[test]
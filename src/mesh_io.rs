@@ -0,0 +1,142 @@
+//! If `mesh-io` is enabled, triangle meshes can be read straight from Wavefront OBJ and STL files.
+
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::TriMesh;
+use crate::util::{index_triangles, Triangle};
+use nalgebra::Point3;
+use obj::{ObjData, ObjError};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Length in bytes of the header of a binary STL file, before the triangle count.
+const STL_HEADER: usize = 80;
+/// Length in bytes of each triangle record in a binary STL file: a normal, three vertices and an
+/// attribute count.
+const STL_RECORD: usize = 50;
+
+impl TriMesh {
+    /// Loads a mesh from the Wavefront OBJ file at `path`. Every face must be a triangle.
+    pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<TriMesh, Error> {
+        TriMesh::read_obj(File::open(path).map_err(|_| Error::MeshRead)?)
+    }
+
+    /// Reads a mesh in Wavefront OBJ format from `input`. Every face must be a triangle.
+    pub fn read_obj<R: Read>(input: R) -> Result<TriMesh, Error> {
+        let data = ObjData::load_buf(input).map_err(|err| match err {
+            ObjError::Io(_) => Error::MeshRead,
+            _ => Error::MalformedMesh,
+        })?;
+        let vertices = data
+            .position
+            .iter()
+            .map(|pos| Point3::new(pos[0], pos[1], pos[2]))
+            .collect();
+
+        let mut faces = Vec::new();
+        for object in data.objects.iter() {
+            for group in object.groups.iter() {
+                for poly in group.polys.iter() {
+                    match poly.0[..] {
+                        [ref a, ref b, ref c] => faces.push([a.0, b.0, c.0]),
+                        _ => return Err(Error::NonTriangularFace),
+                    }
+                }
+            }
+        }
+        TriMesh::new(vertices, faces)
+    }
+
+    /// Loads a mesh from the ASCII or binary STL file at `path`.
+    pub fn load_stl<P: AsRef<Path>>(path: P) -> Result<TriMesh, Error> {
+        TriMesh::read_stl(File::open(path).map_err(|_| Error::MeshRead)?)
+    }
+
+    /// Reads a mesh in ASCII or binary STL format from `input`. STL lists the corners of each
+    /// triangle separately, so corners at identical locations are merged into shared vertices.
+    pub fn read_stl<R: Read>(mut input: R) -> Result<TriMesh, Error> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes).map_err(|_| Error::MeshRead)?;
+        // Binary files may also begin with `solid`, so the length recorded in the header decides
+        let triangles = match binary_stl_count(&bytes) {
+            Some(count) => read_binary_stl(&bytes, count),
+            None if bytes.starts_with(b"solid") => read_ascii_stl(&bytes)?,
+            None => return Err(Error::MalformedMesh),
+        };
+
+        let (vertices, faces) = index_triangles(triangles.iter());
+        TriMesh::new(vertices, faces)
+    }
+}
+
+/// The number of triangles in a binary STL file, if `bytes` is exactly as long as its header claims.
+/// A count too large to address can't match, so isn't taken to be binary.
+fn binary_stl_count(bytes: &[u8]) -> Option<usize> {
+    let count = bytes.get(STL_HEADER..STL_HEADER + 4)?;
+    let count = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize;
+    let length = count.checked_mul(STL_RECORD)?.checked_add(STL_HEADER + 4)?;
+    if bytes.len() == length {
+        Some(count)
+    } else {
+        None
+    }
+}
+
+/// Corners of the `count` triangles in a binary STL file, which has already been checked for length.
+fn read_binary_stl(bytes: &[u8], count: usize) -> Vec<Triangle> {
+    let float =
+        |at: usize| f32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+    let point = |at: usize| Point3::new(float(at), float(at + 4), float(at + 8));
+    (0..count)
+        .map(|idx| {
+            // Skip the normal, which we recalculate from the winding
+            let start = STL_HEADER + 4 + idx * STL_RECORD + 12;
+            (point(start), point(start + 12), point(start + 24))
+        })
+        .collect()
+}
+
+/// Corners of each triangle in an ASCII STL file.
+fn read_ascii_stl(bytes: &[u8]) -> Result<Vec<Triangle>, Error> {
+    let text = std::str::from_utf8(bytes).map_err(|_| Error::MalformedMesh)?;
+    let mut triangles = Vec::new();
+    let mut corners = Vec::new();
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("vertex") => {
+                let coords = words
+                    .map(|word| word.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| Error::MalformedMesh)?;
+                match coords[..] {
+                    [x, y, z] => corners.push(Point3::new(x, y, z)),
+                    _ => return Err(Error::MalformedMesh),
+                }
+            }
+            Some("endloop") => match corners[..] {
+                [a, b, c] => {
+                    triangles.push((a, b, c));
+                    corners.clear();
+                }
+                _ => return Err(Error::NonTriangularFace),
+            },
+            _ => {}
+        }
+    }
+    if corners.is_empty() {
+        Ok(triangles)
+    } else {
+        Err(Error::MalformedMesh)
+    }
+}
+
+#[test]
+fn binary_stl_count_hostile_header() {
+    let mut bytes = vec![0; STL_HEADER + 4 + STL_RECORD];
+    bytes[STL_HEADER..STL_HEADER + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(binary_stl_count(&bytes), None);
+
+    bytes[STL_HEADER..STL_HEADER + 4].copy_from_slice(&1u32.to_le_bytes());
+    assert_eq!(binary_stl_count(&bytes), Some(1));
+}
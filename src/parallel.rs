@@ -0,0 +1,122 @@
+//! A multi-threaded advancing front, available with the `rayon` feature.
+
+use crate::builder::{Limits, PackingObserver, Termination};
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{find_placements, front_grid, separation, Container, Run};
+use nalgebra::Matrix;
+use rand::distributions::Distribution;
+use rand::prelude::SliceRandom;
+use rand::seq::index;
+use rand::Rng;
+use rayon::prelude::*;
+
+/// The parallel counterpart of `advance_front`, visiting up to `batch_size` front spheres at once.
+///
+/// Positions around each sphere in a batch are searched for concurrently, against the packing as
+/// it stood when the batch began. Results are then committed one at a time in a fixed order,
+/// discarding any position that overlaps a sphere placed earlier in the same batch, so the packing
+/// stays free of overlaps and reproducible for a given `rng`. A sphere only leaves the front when
+/// no position was found around it; as the packing only ever grows, that still holds once the
+/// rest of the batch has been committed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn advance_front_parallel<C, D, R>(
+    container: &C,
+    size_distribution: &mut D,
+    rng: &mut R,
+    spheres: &mut Vec<Sphere>,
+    front: &mut Vec<usize>,
+    limits: &Limits,
+    observer: Option<&mut dyn PackingObserver>,
+    batch_size: usize,
+) -> Result<Termination, Error>
+where
+    C: Container + Sync,
+    D: Distribution<f64>,
+    R: Rng + ?Sized,
+{
+    let mut grid = front_grid(spheres);
+    let mut run = Run::new(container, spheres, limits, observer);
+
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut exhausted = Vec::with_capacity(batch_size);
+    loop {
+        if let Some(termination) = run.limit_reached(spheres.len(), front.len()) {
+            return Ok(termination);
+        }
+        let mut amount = batch_size.max(1).min(front.len());
+        if let Some(max) = limits.max_iterations {
+            amount = amount.min(max - run.iterations);
+        }
+        run.iterations += amount;
+
+        // Distinct spheres s₀ from F, each paired with the radius of the sphere to place around it
+        batch.clear();
+        for front_pos in index::sample(rng, front.len(), amount).into_iter() {
+            batch.push((front_pos, size_distribution.sample(rng) as f32));
+        }
+
+        let found = {
+            let snapshot: &[Sphere] = spheres;
+            let grid = &grid;
+            let front: &[usize] = front;
+            batch
+                .par_iter()
+                .map_init(
+                    || (Vec::new(), Vec::new(), Vec::new()),
+                    |(neighbours, set_v, set_f), &(front_pos, radius)| {
+                        find_placements(
+                            container,
+                            snapshot,
+                            grid,
+                            front[front_pos],
+                            radius,
+                            neighbours,
+                            set_v,
+                            set_f,
+                        )?;
+                        Ok(set_f.clone())
+                    },
+                )
+                .collect::<Result<Vec<Vec<Sphere>>, Error>>()?
+        };
+
+        let batch_start = spheres.len();
+        exhausted.clear();
+        for (&(front_pos, _), mut set_f) in batch.iter().zip(found) {
+            if set_f.is_empty() {
+                exhausted.push(front_pos);
+                continue;
+            }
+            // Spheres placed earlier in this batch may have since taken the space
+            set_f.shuffle(rng);
+            let period = container.period();
+            let s_new = set_f.into_iter().find(|s| {
+                !spheres[batch_start..].iter().any(|p| {
+                    Matrix::norm(&separation(&p.center, &s.center, period)) < p.radius + s.radius
+                })
+            });
+            if let Some(s_new) = s_new {
+                front.push(spheres.len());
+                grid.insert(spheres.len(), &s_new);
+                spheres.push(s_new);
+                let event = run
+                    .placed(&spheres[spheres.len() - 1], spheres.len(), front.len())
+                    .or_else(|| run.target_reached(spheres.len()));
+                if let Some(termination) = event {
+                    return Ok(termination);
+                }
+            }
+        }
+
+        // Dropping the highest positions first means a swap never moves another
+        // exhausted sphere out from under us.
+        exhausted.sort_unstable_by(|a, b| b.cmp(a));
+        for &front_pos in exhausted.iter() {
+            front.swap_remove(front_pos);
+            if let Some(termination) = run.exhausted(spheres.len(), front.len()) {
+                return Ok(termination);
+            }
+        }
+    }
+}
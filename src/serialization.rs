@@ -1,14 +1,20 @@
 //! If serde is enabled we need to have the ability to serialize and deserialize all objects in the library.
 
+use nalgebra::{Isometry3, Vector3};
 use serde::de::{self, MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::marker::PhantomData;
 
-use crate::shapes::{Cuboid, Sphere};
-use crate::Container;
+use crate::builder::{Packing, Termination};
+use crate::shapes::{
+    Capsule, Cone, Cuboid, Cylinder, CylindricalShell, Ellipsoid, Frustum, PeriodicCuboid, Sphere,
+    SphericalShell, Transformed, TriMesh,
+};
+use crate::shapes::{Difference, Intersection, Union};
 use crate::PackedVolume;
+use crate::{Container, Disjoint};
 
 impl Serialize for Sphere {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -27,8 +33,210 @@ impl Serialize for Cuboid {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Cuboid", 1)?;
+        // The isometry is omitted for axis aligned cuboids at the origin,
+        // keeping the output identical to that of earlier releases.
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Cuboid", 1 + oriented as usize)?;
         state.serialize_field("half_extents", &self.half_extents)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for Capsule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Capsule", 2 + oriented as usize)?;
+        state.serialize_field("radius", &self.radius)?;
+        state.serialize_field("height", &self.height)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for Cone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Cone", 2 + oriented as usize)?;
+        state.serialize_field("radius", &self.radius)?;
+        state.serialize_field("height", &self.height)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for Cylinder {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Cylinder", 2 + oriented as usize)?;
+        state.serialize_field("radius", &self.radius)?;
+        state.serialize_field("height", &self.height)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for CylindricalShell {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("CylindricalShell", 3 + oriented as usize)?;
+        state.serialize_field("inner_radius", &self.inner_radius)?;
+        state.serialize_field("outer_radius", &self.outer_radius)?;
+        state.serialize_field("height", &self.height)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for Ellipsoid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Ellipsoid", 1 + oriented as usize)?;
+        state.serialize_field("semi_axes", &self.semi_axes)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for Frustum {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let oriented = self.isometry != Isometry3::identity();
+        let mut state = serializer.serialize_struct("Frustum", 3 + oriented as usize)?;
+        state.serialize_field("bottom_radius", &self.bottom_radius)?;
+        state.serialize_field("top_radius", &self.top_radius)?;
+        state.serialize_field("height", &self.height)?;
+        if oriented {
+            state.serialize_field("isometry", &self.isometry)?;
+        } else {
+            state.skip_field("isometry")?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for PeriodicCuboid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("PeriodicCuboid", 1)?;
+        state.serialize_field("half_extents", &self.half_extents)?;
+        state.end()
+    }
+}
+
+impl Serialize for SphericalShell {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SphericalShell", 3)?;
+        state.serialize_field("center", &self.center)?;
+        state.serialize_field("inner_radius", &self.inner_radius)?;
+        state.serialize_field("outer_radius", &self.outer_radius)?;
+        state.end()
+    }
+}
+
+impl Serialize for TriMesh {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TriMesh", 2)?;
+        state.serialize_field("vertices", self.vertices())?;
+        state.serialize_field("faces", self.faces())?;
+        state.end()
+    }
+}
+
+impl<C: Serialize> Serialize for Transformed<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Transformed", 3)?;
+        state.serialize_field("inner", &self.inner)?;
+        state.serialize_field("isometry", &self.isometry)?;
+        state.serialize_field("scale", &self.scale)?;
+        state.end()
+    }
+}
+
+impl<A: Serialize, B: Serialize> Serialize for Union<A, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Union", 2)?;
+        state.serialize_field("first", self.first())?;
+        state.serialize_field("second", self.second())?;
+        state.end()
+    }
+}
+
+impl<A: Serialize, B: Serialize> Serialize for Intersection<A, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Intersection", 2)?;
+        state.serialize_field("first", self.first())?;
+        state.serialize_field("second", self.second())?;
+        state.end()
+    }
+}
+
+impl<A: Serialize, B: Serialize> Serialize for Difference<A, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Difference", 2)?;
+        state.serialize_field("first", self.first())?;
+        state.serialize_field("second", self.second())?;
         state.end()
     }
 }
@@ -53,7 +261,7 @@ impl<'de> Deserialize<'de> for Sphere {
         enum Field {
             Center,
             Radius,
-        };
+        }
 
         impl<'de> Deserialize<'de> for Field {
             fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
@@ -104,7 +312,7 @@ impl<'de> Deserialize<'de> for Sphere {
                 let radius = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
-                Ok(Sphere::new(center, radius).map_err(de::Error::custom)?)
+                Sphere::new(center, radius).map_err(de::Error::custom)
             }
 
             fn visit_map<V>(self, mut map: V) -> Result<Sphere, V::Error>
@@ -131,7 +339,7 @@ impl<'de> Deserialize<'de> for Sphere {
                 }
                 let center = center.ok_or_else(|| de::Error::missing_field("center"))?;
                 let radius = radius.ok_or_else(|| de::Error::missing_field("radius"))?;
-                Ok(Sphere::new(center, radius).map_err(de::Error::custom)?)
+                Sphere::new(center, radius).map_err(de::Error::custom)
             }
         }
 
@@ -147,7 +355,8 @@ impl<'de> Deserialize<'de> for Cuboid {
     {
         enum Field {
             HalfExtents,
-        };
+            Isometry,
+        }
 
         impl<'de> Deserialize<'de> for Field {
             fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
@@ -160,7 +369,7 @@ impl<'de> Deserialize<'de> for Cuboid {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`half_extents`")
+                        formatter.write_str("`half_extents` or `isometry`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -169,6 +378,7 @@ impl<'de> Deserialize<'de> for Cuboid {
                     {
                         match value {
                             "half_extents" => Ok(Field::HalfExtents),
+                            "isometry" => Ok(Field::Isometry),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -194,7 +404,10 @@ impl<'de> Deserialize<'de> for Cuboid {
                 let half_extents = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                Ok(Cuboid::from_vec(half_extents).map_err(de::Error::custom)?)
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Cuboid::from_vec(half_extents)
+                    .map(|cuboid| cuboid.with_isometry(isometry))
+                    .map_err(de::Error::custom)
             }
 
             fn visit_map<V>(self, mut map: V) -> Result<Cuboid, V::Error>
@@ -202,6 +415,7 @@ impl<'de> Deserialize<'de> for Cuboid {
                 V: MapAccess<'de>,
             {
                 let mut half_extents = None;
+                let mut isometry = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::HalfExtents => {
@@ -210,28 +424,38 @@ impl<'de> Deserialize<'de> for Cuboid {
                             }
                             half_extents = Some(map.next_value()?);
                         }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
                     }
                 }
                 let half_extents =
                     half_extents.ok_or_else(|| de::Error::missing_field("half_extents"))?;
-                Ok(Cuboid::from_vec(half_extents).map_err(de::Error::custom)?)
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Cuboid::from_vec(half_extents)
+                    .map(|cuboid| cuboid.with_isometry(isometry))
+                    .map_err(de::Error::custom)
             }
         }
 
-        const FIELDS: &[&str] = &["half_extents"];
+        const FIELDS: &[&str] = &["half_extents", "isometry"];
         deserializer.deserialize_struct("Cuboid", FIELDS, CuboidVisitor)
     }
 }
 
-impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for PackedVolume<C> {
+impl<'de> Deserialize<'de> for Capsule {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         enum Field {
-            Spheres,
-            Container,
-        };
+            Radius,
+            Height,
+            Isometry,
+        }
 
         impl<'de> Deserialize<'de> for Field {
             fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
@@ -244,7 +468,7 @@ impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for PackedVolume<C>
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`spheres` or `container`")
+                        formatter.write_str("`radius`, `height` or `isometry`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -252,8 +476,9 @@ impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for PackedVolume<C>
                         E: de::Error,
                     {
                         match value {
-                            "spheres" => Ok(Field::Spheres),
-                            "container" => Ok(Field::Container),
+                            "radius" => Ok(Field::Radius),
+                            "height" => Ok(Field::Height),
+                            "isometry" => Ok(Field::Isometry),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -263,60 +488,1642 @@ impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for PackedVolume<C>
             }
         }
 
-        struct PackedVolumeVisitor<P>(PhantomData<fn() -> P>);
+        struct CapsuleVisitor;
 
-        impl<'de, P> Visitor<'de> for PackedVolumeVisitor<P>
-        where
-            P: Deserialize<'de> + Container,
-        {
-            type Value = PackedVolume<P>;
+        impl<'de> Visitor<'de> for CapsuleVisitor {
+            type Value = Capsule;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct PackedVolume")
+                formatter.write_str("struct Capsule")
             }
 
-            fn visit_seq<V>(self, mut seq: V) -> Result<PackedVolume<P>, V::Error>
+            fn visit_seq<V>(self, mut seq: V) -> Result<Capsule, V::Error>
             where
                 V: SeqAccess<'de>,
             {
-                let spheres = seq
+                let radius = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                let container = seq
+                let height = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
-                Ok(PackedVolume::from_vec(spheres, container))
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Capsule::new(radius, height)
+                    .map(|capsule| capsule.with_isometry(isometry))
+                    .map_err(de::Error::custom)
             }
 
-            fn visit_map<V>(self, mut map: V) -> Result<PackedVolume<P>, V::Error>
+            fn visit_map<V>(self, mut map: V) -> Result<Capsule, V::Error>
             where
                 V: MapAccess<'de>,
             {
-                let mut spheres = None;
-                let mut container = None;
+                let mut radius = None;
+                let mut height = None;
+                let mut isometry = None;
                 while let Some(key) = map.next_key()? {
                     match key {
-                        Field::Spheres => {
-                            if spheres.is_some() {
-                                return Err(de::Error::duplicate_field("spheres"));
+                        Field::Radius => {
+                            if radius.is_some() {
+                                return Err(de::Error::duplicate_field("radius"));
                             }
-                            spheres = Some(map.next_value()?);
+                            radius = Some(map.next_value()?);
                         }
-                        Field::Container => {
-                            if container.is_some() {
-                                return Err(de::Error::duplicate_field("container"));
+                        Field::Height => {
+                            if height.is_some() {
+                                return Err(de::Error::duplicate_field("height"));
                             }
-                            container = Some(map.next_value()?);
+                            height = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
                         }
                     }
                 }
-                let spheres = spheres.ok_or_else(|| de::Error::missing_field("spheres"))?;
-                let container = container.ok_or_else(|| de::Error::missing_field("container"))?;
-                Ok(PackedVolume::from_vec(spheres, container))
+                let radius = radius.ok_or_else(|| de::Error::missing_field("radius"))?;
+                let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Capsule::new(radius, height)
+                    .map(|capsule| capsule.with_isometry(isometry))
+                    .map_err(de::Error::custom)
             }
         }
 
-        const FIELDS: &[&str] = &["spheres", "container"];
-        deserializer.deserialize_struct("PackedVolume", FIELDS, PackedVolumeVisitor(PhantomData))
+        const FIELDS: &[&str] = &["radius", "height", "isometry"];
+        deserializer.deserialize_struct("Capsule", FIELDS, CapsuleVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Radius,
+            Height,
+            Isometry,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`radius`, `height` or `isometry`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "radius" => Ok(Field::Radius),
+                            "height" => Ok(Field::Height),
+                            "isometry" => Ok(Field::Isometry),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct ConeVisitor;
+
+        impl<'de> Visitor<'de> for ConeVisitor {
+            type Value = Cone;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Cone")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Cone, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let height = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Cone::new(radius, height)
+                    .map(|cone| cone.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Cone, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut radius = None;
+                let mut height = None;
+                let mut isometry = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Radius => {
+                            if radius.is_some() {
+                                return Err(de::Error::duplicate_field("radius"));
+                            }
+                            radius = Some(map.next_value()?);
+                        }
+                        Field::Height => {
+                            if height.is_some() {
+                                return Err(de::Error::duplicate_field("height"));
+                            }
+                            height = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let radius = radius.ok_or_else(|| de::Error::missing_field("radius"))?;
+                let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Cone::new(radius, height)
+                    .map(|cone| cone.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["radius", "height", "isometry"];
+        deserializer.deserialize_struct("Cone", FIELDS, ConeVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cylinder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Radius,
+            Height,
+            Isometry,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`radius`, `height` or `isometry`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "radius" => Ok(Field::Radius),
+                            "height" => Ok(Field::Height),
+                            "isometry" => Ok(Field::Isometry),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct CylinderVisitor;
+
+        impl<'de> Visitor<'de> for CylinderVisitor {
+            type Value = Cylinder;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Cylinder")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Cylinder, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let height = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Cylinder::new(radius, height)
+                    .map(|cylinder| cylinder.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Cylinder, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut radius = None;
+                let mut height = None;
+                let mut isometry = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Radius => {
+                            if radius.is_some() {
+                                return Err(de::Error::duplicate_field("radius"));
+                            }
+                            radius = Some(map.next_value()?);
+                        }
+                        Field::Height => {
+                            if height.is_some() {
+                                return Err(de::Error::duplicate_field("height"));
+                            }
+                            height = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let radius = radius.ok_or_else(|| de::Error::missing_field("radius"))?;
+                let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Cylinder::new(radius, height)
+                    .map(|cylinder| cylinder.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["radius", "height", "isometry"];
+        deserializer.deserialize_struct("Cylinder", FIELDS, CylinderVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for CylindricalShell {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            InnerRadius,
+            OuterRadius,
+            Height,
+            Isometry,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter
+                            .write_str("`inner_radius`, `outer_radius`, `height` or `isometry`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "inner_radius" => Ok(Field::InnerRadius),
+                            "outer_radius" => Ok(Field::OuterRadius),
+                            "height" => Ok(Field::Height),
+                            "isometry" => Ok(Field::Isometry),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct CylindricalShellVisitor;
+
+        impl<'de> Visitor<'de> for CylindricalShellVisitor {
+            type Value = CylindricalShell;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct CylindricalShell")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<CylindricalShell, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let inner_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let outer_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let height = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                CylindricalShell::new(inner_radius, outer_radius, height)
+                    .map(|shell| shell.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<CylindricalShell, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut inner_radius = None;
+                let mut outer_radius = None;
+                let mut height = None;
+                let mut isometry = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::InnerRadius => {
+                            if inner_radius.is_some() {
+                                return Err(de::Error::duplicate_field("inner_radius"));
+                            }
+                            inner_radius = Some(map.next_value()?);
+                        }
+                        Field::OuterRadius => {
+                            if outer_radius.is_some() {
+                                return Err(de::Error::duplicate_field("outer_radius"));
+                            }
+                            outer_radius = Some(map.next_value()?);
+                        }
+                        Field::Height => {
+                            if height.is_some() {
+                                return Err(de::Error::duplicate_field("height"));
+                            }
+                            height = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let inner_radius =
+                    inner_radius.ok_or_else(|| de::Error::missing_field("inner_radius"))?;
+                let outer_radius =
+                    outer_radius.ok_or_else(|| de::Error::missing_field("outer_radius"))?;
+                let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                CylindricalShell::new(inner_radius, outer_radius, height)
+                    .map(|shell| shell.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["inner_radius", "outer_radius", "height", "isometry"];
+        deserializer.deserialize_struct("CylindricalShell", FIELDS, CylindricalShellVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ellipsoid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            SemiAxes,
+            Isometry,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`semi_axes` or `isometry`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "semi_axes" => Ok(Field::SemiAxes),
+                            "isometry" => Ok(Field::Isometry),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct EllipsoidVisitor;
+
+        impl<'de> Visitor<'de> for EllipsoidVisitor {
+            type Value = Ellipsoid;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Ellipsoid")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Ellipsoid, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let semi_axes: Vector3<f32> = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Ellipsoid::new(semi_axes.x, semi_axes.y, semi_axes.z)
+                    .map(|ellipsoid| ellipsoid.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Ellipsoid, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut semi_axes = None;
+                let mut isometry = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::SemiAxes => {
+                            if semi_axes.is_some() {
+                                return Err(de::Error::duplicate_field("semi_axes"));
+                            }
+                            semi_axes = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let semi_axes: Vector3<f32> =
+                    semi_axes.ok_or_else(|| de::Error::missing_field("semi_axes"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Ellipsoid::new(semi_axes.x, semi_axes.y, semi_axes.z)
+                    .map(|ellipsoid| ellipsoid.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["semi_axes", "isometry"];
+        deserializer.deserialize_struct("Ellipsoid", FIELDS, EllipsoidVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Frustum {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            BottomRadius,
+            TopRadius,
+            Height,
+            Isometry,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`bottom_radius`, `top_radius`, `height` or `isometry`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "bottom_radius" => Ok(Field::BottomRadius),
+                            "top_radius" => Ok(Field::TopRadius),
+                            "height" => Ok(Field::Height),
+                            "isometry" => Ok(Field::Isometry),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct FrustumVisitor;
+
+        impl<'de> Visitor<'de> for FrustumVisitor {
+            type Value = Frustum;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Frustum")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Frustum, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let bottom_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let top_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let height = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let isometry = seq.next_element()?.unwrap_or_else(Isometry3::identity);
+                Frustum::new(bottom_radius, top_radius, height)
+                    .map(|frustum| frustum.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Frustum, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut bottom_radius = None;
+                let mut top_radius = None;
+                let mut height = None;
+                let mut isometry = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::BottomRadius => {
+                            if bottom_radius.is_some() {
+                                return Err(de::Error::duplicate_field("bottom_radius"));
+                            }
+                            bottom_radius = Some(map.next_value()?);
+                        }
+                        Field::TopRadius => {
+                            if top_radius.is_some() {
+                                return Err(de::Error::duplicate_field("top_radius"));
+                            }
+                            top_radius = Some(map.next_value()?);
+                        }
+                        Field::Height => {
+                            if height.is_some() {
+                                return Err(de::Error::duplicate_field("height"));
+                            }
+                            height = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let bottom_radius =
+                    bottom_radius.ok_or_else(|| de::Error::missing_field("bottom_radius"))?;
+                let top_radius =
+                    top_radius.ok_or_else(|| de::Error::missing_field("top_radius"))?;
+                let height = height.ok_or_else(|| de::Error::missing_field("height"))?;
+                let isometry = isometry.unwrap_or_else(Isometry3::identity);
+                Frustum::new(bottom_radius, top_radius, height)
+                    .map(|frustum| frustum.with_isometry(isometry))
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["bottom_radius", "top_radius", "height", "isometry"];
+        deserializer.deserialize_struct("Frustum", FIELDS, FrustumVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for PeriodicCuboid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            HalfExtents,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`half_extents`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "half_extents" => Ok(Field::HalfExtents),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct PeriodicCuboidVisitor;
+
+        impl<'de> Visitor<'de> for PeriodicCuboidVisitor {
+            type Value = PeriodicCuboid;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct PeriodicCuboid")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<PeriodicCuboid, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let half_extents = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                PeriodicCuboid::from_vec(half_extents).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<PeriodicCuboid, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut half_extents = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::HalfExtents => {
+                            if half_extents.is_some() {
+                                return Err(de::Error::duplicate_field("half_extents"));
+                            }
+                            half_extents = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let half_extents =
+                    half_extents.ok_or_else(|| de::Error::missing_field("half_extents"))?;
+                PeriodicCuboid::from_vec(half_extents).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["half_extents"];
+        deserializer.deserialize_struct("PeriodicCuboid", FIELDS, PeriodicCuboidVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for SphericalShell {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Center,
+            InnerRadius,
+            OuterRadius,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`center`, `inner_radius` or `outer_radius`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "center" => Ok(Field::Center),
+                            "inner_radius" => Ok(Field::InnerRadius),
+                            "outer_radius" => Ok(Field::OuterRadius),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct SphericalShellVisitor;
+
+        impl<'de> Visitor<'de> for SphericalShellVisitor {
+            type Value = SphericalShell;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct SphericalShell")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<SphericalShell, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let center = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let inner_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let outer_radius = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                SphericalShell::new(center, inner_radius, outer_radius).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<SphericalShell, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut center = None;
+                let mut inner_radius = None;
+                let mut outer_radius = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Center => {
+                            if center.is_some() {
+                                return Err(de::Error::duplicate_field("center"));
+                            }
+                            center = Some(map.next_value()?);
+                        }
+                        Field::InnerRadius => {
+                            if inner_radius.is_some() {
+                                return Err(de::Error::duplicate_field("inner_radius"));
+                            }
+                            inner_radius = Some(map.next_value()?);
+                        }
+                        Field::OuterRadius => {
+                            if outer_radius.is_some() {
+                                return Err(de::Error::duplicate_field("outer_radius"));
+                            }
+                            outer_radius = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let center = center.ok_or_else(|| de::Error::missing_field("center"))?;
+                let inner_radius =
+                    inner_radius.ok_or_else(|| de::Error::missing_field("inner_radius"))?;
+                let outer_radius =
+                    outer_radius.ok_or_else(|| de::Error::missing_field("outer_radius"))?;
+                SphericalShell::new(center, inner_radius, outer_radius).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["center", "inner_radius", "outer_radius"];
+        deserializer.deserialize_struct("SphericalShell", FIELDS, SphericalShellVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for TriMesh {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Vertices,
+            Faces,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`vertices` or `faces`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "vertices" => Ok(Field::Vertices),
+                            "faces" => Ok(Field::Faces),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct TriMeshVisitor;
+
+        impl<'de> Visitor<'de> for TriMeshVisitor {
+            type Value = TriMesh;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct TriMesh")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<TriMesh, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let vertices = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let faces = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                TriMesh::new(vertices, faces).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<TriMesh, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut vertices = None;
+                let mut faces = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Vertices => {
+                            if vertices.is_some() {
+                                return Err(de::Error::duplicate_field("vertices"));
+                            }
+                            vertices = Some(map.next_value()?);
+                        }
+                        Field::Faces => {
+                            if faces.is_some() {
+                                return Err(de::Error::duplicate_field("faces"));
+                            }
+                            faces = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let vertices = vertices.ok_or_else(|| de::Error::missing_field("vertices"))?;
+                let faces = faces.ok_or_else(|| de::Error::missing_field("faces"))?;
+                TriMesh::new(vertices, faces).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["vertices", "faces"];
+        deserializer.deserialize_struct("TriMesh", FIELDS, TriMeshVisitor)
+    }
+}
+
+impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for Transformed<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Inner,
+            Isometry,
+            Scale,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`inner`, `isometry` or `scale`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "inner" => Ok(Field::Inner),
+                            "isometry" => Ok(Field::Isometry),
+                            "scale" => Ok(Field::Scale),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct TransformedVisitor<P>(PhantomData<fn() -> P>);
+
+        impl<'de, P> Visitor<'de> for TransformedVisitor<P>
+        where
+            P: Deserialize<'de> + Container,
+        {
+            type Value = Transformed<P>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Transformed")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Transformed<P>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let inner = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let isometry = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let scale = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                Transformed::new(inner, isometry, scale).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Transformed<P>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut inner = None;
+                let mut isometry = None;
+                let mut scale = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Inner => {
+                            if inner.is_some() {
+                                return Err(de::Error::duplicate_field("inner"));
+                            }
+                            inner = Some(map.next_value()?);
+                        }
+                        Field::Isometry => {
+                            if isometry.is_some() {
+                                return Err(de::Error::duplicate_field("isometry"));
+                            }
+                            isometry = Some(map.next_value()?);
+                        }
+                        Field::Scale => {
+                            if scale.is_some() {
+                                return Err(de::Error::duplicate_field("scale"));
+                            }
+                            scale = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let inner = inner.ok_or_else(|| de::Error::missing_field("inner"))?;
+                let isometry = isometry.ok_or_else(|| de::Error::missing_field("isometry"))?;
+                let scale = scale.ok_or_else(|| de::Error::missing_field("scale"))?;
+                Transformed::new(inner, isometry, scale).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["inner", "isometry", "scale"];
+        deserializer.deserialize_struct("Transformed", FIELDS, TransformedVisitor(PhantomData))
+    }
+}
+
+impl<'de, A, B> Deserialize<'de> for Union<A, B>
+where
+    A: Container + Deserialize<'de>,
+    B: Container + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            First,
+            Second,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`first` or `second`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "first" => Ok(Field::First),
+                            "second" => Ok(Field::Second),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct UnionVisitor<P, Q>(PhantomData<fn() -> (P, Q)>);
+
+        impl<'de, P, Q> Visitor<'de> for UnionVisitor<P, Q>
+        where
+            P: Container + Deserialize<'de>,
+            Q: Container + Deserialize<'de>,
+        {
+            type Value = Union<P, Q>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Union")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Union<P, Q>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let first = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let second = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Union::new(first, second).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Union<P, Q>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut first = None;
+                let mut second = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::First => {
+                            if first.is_some() {
+                                return Err(de::Error::duplicate_field("first"));
+                            }
+                            first = Some(map.next_value()?);
+                        }
+                        Field::Second => {
+                            if second.is_some() {
+                                return Err(de::Error::duplicate_field("second"));
+                            }
+                            second = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let first = first.ok_or_else(|| de::Error::missing_field("first"))?;
+                let second = second.ok_or_else(|| de::Error::missing_field("second"))?;
+                Union::new(first, second).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["first", "second"];
+        deserializer.deserialize_struct("Union", FIELDS, UnionVisitor(PhantomData))
+    }
+}
+
+impl<'de, A, B> Deserialize<'de> for Intersection<A, B>
+where
+    A: Container + Deserialize<'de>,
+    B: Container + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            First,
+            Second,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`first` or `second`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "first" => Ok(Field::First),
+                            "second" => Ok(Field::Second),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct IntersectionVisitor<P, Q>(PhantomData<fn() -> (P, Q)>);
+
+        impl<'de, P, Q> Visitor<'de> for IntersectionVisitor<P, Q>
+        where
+            P: Container + Deserialize<'de>,
+            Q: Container + Deserialize<'de>,
+        {
+            type Value = Intersection<P, Q>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Intersection")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Intersection<P, Q>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let first = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let second = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Intersection::new(first, second).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Intersection<P, Q>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut first = None;
+                let mut second = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::First => {
+                            if first.is_some() {
+                                return Err(de::Error::duplicate_field("first"));
+                            }
+                            first = Some(map.next_value()?);
+                        }
+                        Field::Second => {
+                            if second.is_some() {
+                                return Err(de::Error::duplicate_field("second"));
+                            }
+                            second = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let first = first.ok_or_else(|| de::Error::missing_field("first"))?;
+                let second = second.ok_or_else(|| de::Error::missing_field("second"))?;
+                Intersection::new(first, second).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["first", "second"];
+        deserializer.deserialize_struct("Intersection", FIELDS, IntersectionVisitor(PhantomData))
+    }
+}
+
+impl<'de, A, B> Deserialize<'de> for Difference<A, B>
+where
+    A: Container + Deserialize<'de>,
+    B: Container + Disjoint + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            First,
+            Second,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`first` or `second`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "first" => Ok(Field::First),
+                            "second" => Ok(Field::Second),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct DifferenceVisitor<P, Q>(PhantomData<fn() -> (P, Q)>);
+
+        impl<'de, P, Q> Visitor<'de> for DifferenceVisitor<P, Q>
+        where
+            P: Container + Deserialize<'de>,
+            Q: Container + Disjoint + Deserialize<'de>,
+        {
+            type Value = Difference<P, Q>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Difference")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Difference<P, Q>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let first = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let second = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Difference::new(first, second).map_err(de::Error::custom)
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Difference<P, Q>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut first = None;
+                let mut second = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::First => {
+                            if first.is_some() {
+                                return Err(de::Error::duplicate_field("first"));
+                            }
+                            first = Some(map.next_value()?);
+                        }
+                        Field::Second => {
+                            if second.is_some() {
+                                return Err(de::Error::duplicate_field("second"));
+                            }
+                            second = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let first = first.ok_or_else(|| de::Error::missing_field("first"))?;
+                let second = second.ok_or_else(|| de::Error::missing_field("second"))?;
+                Difference::new(first, second).map_err(de::Error::custom)
+            }
+        }
+
+        const FIELDS: &[&str] = &["first", "second"];
+        deserializer.deserialize_struct("Difference", FIELDS, DifferenceVisitor(PhantomData))
+    }
+}
+
+impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for PackedVolume<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Spheres,
+            Container,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`spheres` or `container`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "spheres" => Ok(Field::Spheres),
+                            "container" => Ok(Field::Container),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct PackedVolumeVisitor<P>(PhantomData<fn() -> P>);
+
+        impl<'de, P> Visitor<'de> for PackedVolumeVisitor<P>
+        where
+            P: Deserialize<'de> + Container,
+        {
+            type Value = PackedVolume<P>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct PackedVolume")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<PackedVolume<P>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let spheres = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let container = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(PackedVolume::from_vec(spheres, container))
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<PackedVolume<P>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut spheres = None;
+                let mut container = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Spheres => {
+                            if spheres.is_some() {
+                                return Err(de::Error::duplicate_field("spheres"));
+                            }
+                            spheres = Some(map.next_value()?);
+                        }
+                        Field::Container => {
+                            if container.is_some() {
+                                return Err(de::Error::duplicate_field("container"));
+                            }
+                            container = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let spheres = spheres.ok_or_else(|| de::Error::missing_field("spheres"))?;
+                let container = container.ok_or_else(|| de::Error::missing_field("container"))?;
+                Ok(PackedVolume::from_vec(spheres, container))
+            }
+        }
+
+        const FIELDS: &[&str] = &["spheres", "container"];
+        deserializer.deserialize_struct("PackedVolume", FIELDS, PackedVolumeVisitor(PhantomData))
+    }
+}
+
+impl Termination {
+    fn name(self) -> &'static str {
+        match self {
+            Termination::FrontExhausted => "FrontExhausted",
+            Termination::SphereCount => "SphereCount",
+            Termination::VolumeFraction => "VolumeFraction",
+            Termination::TimeBudget => "TimeBudget",
+            Termination::Iterations => "Iterations",
+            Termination::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl Serialize for Termination {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Termination {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TerminationVisitor;
+
+        impl<'de> Visitor<'de> for TerminationVisitor {
+            type Value = Termination;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a packing termination criterion")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Termination, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "FrontExhausted" => Ok(Termination::FrontExhausted),
+                    "SphereCount" => Ok(Termination::SphereCount),
+                    "VolumeFraction" => Ok(Termination::VolumeFraction),
+                    "TimeBudget" => Ok(Termination::TimeBudget),
+                    "Iterations" => Ok(Termination::Iterations),
+                    "Cancelled" => Ok(Termination::Cancelled),
+                    _ => Err(de::Error::unknown_variant(value, VARIANTS)),
+                }
+            }
+        }
+
+        const VARIANTS: &[&str] = &[
+            "FrontExhausted",
+            "SphereCount",
+            "VolumeFraction",
+            "TimeBudget",
+            "Iterations",
+            "Cancelled",
+        ];
+        deserializer.deserialize_str(TerminationVisitor)
+    }
+}
+
+impl<C: Container + Serialize> Serialize for Packing<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Packing", 3)?;
+        state.serialize_field("packed", &self.packed)?;
+        state.serialize_field("front", &self.front)?;
+        state.serialize_field("termination", &self.termination)?;
+        state.end()
+    }
+}
+
+impl<'de, C: Container + Deserialize<'de>> Deserialize<'de> for Packing<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Packed,
+            Front,
+            Termination,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl<'de> Visitor<'de> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("`packed`, `front` or `termination`")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "packed" => Ok(Field::Packed),
+                            "front" => Ok(Field::Front),
+                            "termination" => Ok(Field::Termination),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct PackingVisitor<P>(PhantomData<fn() -> P>);
+
+        impl<'de, P> Visitor<'de> for PackingVisitor<P>
+        where
+            P: Deserialize<'de> + Container,
+        {
+            type Value = Packing<P>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct Packing")
+            }
+
+            fn visit_seq<V>(self, mut seq: V) -> Result<Packing<P>, V::Error>
+            where
+                V: SeqAccess<'de>,
+            {
+                let packed = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let front = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let termination = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                Ok(Packing {
+                    packed,
+                    front,
+                    termination,
+                })
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Packing<P>, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut packed = None;
+                let mut front = None;
+                let mut termination = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Packed => {
+                            if packed.is_some() {
+                                return Err(de::Error::duplicate_field("packed"));
+                            }
+                            packed = Some(map.next_value()?);
+                        }
+                        Field::Front => {
+                            if front.is_some() {
+                                return Err(de::Error::duplicate_field("front"));
+                            }
+                            front = Some(map.next_value()?);
+                        }
+                        Field::Termination => {
+                            if termination.is_some() {
+                                return Err(de::Error::duplicate_field("termination"));
+                            }
+                            termination = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let packed = packed.ok_or_else(|| de::Error::missing_field("packed"))?;
+                let front = front.ok_or_else(|| de::Error::missing_field("front"))?;
+                let termination =
+                    termination.ok_or_else(|| de::Error::missing_field("termination"))?;
+                Ok(Packing {
+                    packed,
+                    front,
+                    termination,
+                })
+            }
+        }
+
+        const FIELDS: &[&str] = &["packed", "front", "termination"];
+        deserializer.deserialize_struct("Packing", FIELDS, PackingVisitor(PhantomData))
     }
 }
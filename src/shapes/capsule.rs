@@ -0,0 +1,87 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3, Vector3};
+use std::f32::consts::{FRAC_PI_3, PI};
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs a capsule (a cylinder capped by two hemispheres) in Euclidean space. By default the
+/// capsule's axis is aligned with z and its center sits at the origin, but it may be moved and
+/// rotated arbitrarily with an `isometry`.
+pub struct Capsule {
+    /// Radius of the cylindrical section and both hemispherical caps.
+    pub radius: f32,
+    /// Length of the cylindrical section, i.e. the distance between the centers of the two caps.
+    pub height: f32,
+    /// Rigid transform taking the capsule from its z aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl Capsule {
+    /// Creates a new capsule from its `radius`, which must be greater than 0, and the `height`
+    /// of its cylindrical section, which must not be negative.
+    pub fn new(radius: f32, height: f32) -> Result<Capsule, Error> {
+        if radius <= 0.0 {
+            Err(Error::NegativeRadius)
+        } else if height < 0.0 {
+            Err(Error::NegativeHeight)
+        } else {
+            Ok(Capsule {
+                radius,
+                height,
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this capsule into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Capsule {
+        self.isometry = isometry;
+        self
+    }
+
+    /// Distance from `point` to the nearest point on the capsule's axial segment.
+    fn axis_distance(&self, point: &Point3<f32>) -> f32 {
+        let point = self.isometry.inverse_transform_point(point);
+        let half_height = 0.5 * self.height;
+        let nearest = Point3::new(0., 0., point.z.clamp(-half_height, half_height));
+        nalgebra::distance(&point, &nearest)
+    }
+}
+
+impl Container for Capsule {
+    /// Checks if sphere exists inside the current capsule.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        // The capsule holds every point within `radius` of its axial segment, so the sphere
+        // must lie at least its own radius closer than that
+        self.axis_distance(&sphere.center) + sphere.radius <= self.radius
+    }
+
+    /// Calculates the volume of this capsule in normalised units.
+    fn volume(&self) -> f32 {
+        PI * self.radius.powi(2) * self.height + 4. * FRAC_PI_3 * self.radius.powi(3)
+    }
+
+    /// Packing starts from the center of this capsule.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::from(self.isometry.translation.vector)
+    }
+
+    /// The axis aligned box enclosing this capsule, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let half_extents = Vector3::new(self.radius, self.radius, 0.5 * self.height + self.radius);
+        let rotation = self.isometry.rotation.to_rotation_matrix();
+        let reach = rotation.matrix().abs() * half_extents;
+        let center = self.seed_point();
+        Some((center - reach, center + reach))
+    }
+}
+
+impl Disjoint for Capsule {
+    /// Checks if sphere lies completely outside the current capsule.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.axis_distance(&sphere.center) >= self.radius + sphere.radius
+    }
+}
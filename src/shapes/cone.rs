@@ -0,0 +1,77 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::frustum::{in_truncated_cone, truncated_cone_bounds, truncated_cone_gap};
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3};
+use std::f32::consts::FRAC_PI_3;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs a right circular cone in Euclidean space. By default the cone's axis is aligned
+/// with z and its center sits at the origin, with the base at `-height / 2` and the apex at
+/// `height / 2`. It may be moved and rotated arbitrarily with an `isometry`.
+pub struct Cone {
+    /// Radius of the circular base.
+    pub radius: f32,
+    /// Distance from the base to the apex.
+    pub height: f32,
+    /// Rigid transform taking the cone from its z aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl Cone {
+    /// Creates a new cone from its base `radius` and `height`, both of which must be greater than 0.
+    pub fn new(radius: f32, height: f32) -> Result<Cone, Error> {
+        if radius <= 0.0 {
+            Err(Error::NegativeRadius)
+        } else if height <= 0.0 {
+            Err(Error::NegativeHeight)
+        } else {
+            Ok(Cone {
+                radius,
+                height,
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this cone into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Cone {
+        self.isometry = isometry;
+        self
+    }
+}
+
+impl Container for Cone {
+    /// Checks if sphere exists inside the current cone.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        in_truncated_cone(self.radius, 0., self.height, &center, sphere.radius)
+    }
+
+    /// Calculates the volume of this cone in normalised units.
+    fn volume(&self) -> f32 {
+        FRAC_PI_3 * self.radius.powi(2) * self.height
+    }
+
+    /// Packing starts a quarter of the way up the axis, the center of mass of this cone,
+    /// where there is the most room.
+    fn seed_point(&self) -> Point3<f32> {
+        self.isometry
+            .transform_point(&Point3::new(0., 0., -0.25 * self.height))
+    }
+
+    /// The axis aligned box enclosing this cone, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        truncated_cone_bounds(self.radius, self.height, &self.isometry)
+    }
+}
+
+impl Disjoint for Cone {
+    /// Checks if sphere lies completely outside the current cone.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        truncated_cone_gap(self.radius, 0., self.height, &center) >= sphere.radius
+    }
+}
@@ -0,0 +1,378 @@
+//! Constructive solid geometry: containers built by combining two others.
+
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::{Integrator, Sphere};
+use crate::{Container, Disjoint};
+use nalgebra::{Point3, Vector3};
+use std::f32::consts::{FRAC_PI_3, PI};
+
+/// Number of points sampled by `new` when the volume shared by two containers has to be
+/// estimated. Use `with_integrator` to choose another sample count or a grid.
+pub const VOLUME_SAMPLES: usize = 200_000;
+
+/// How `new` estimates the volume shared by two containers.
+const DEFAULT_INTEGRATOR: Integrator = Integrator::MonteCarlo {
+    samples: VOLUME_SAMPLES,
+};
+
+/// How many times the octants of a sphere are split while showing that it lies within a
+/// `Union`, though within neither of its containers alone.
+const SEAM_DEPTH: usize = 3;
+
+type Bounds = (Point3<f32>, Point3<f32>);
+
+/// The bounding box of `container`, which combinators need to find their volume.
+fn bounds<C: Container>(container: &C) -> Result<Bounds, Error> {
+    container.bounding_box().ok_or(Error::Unbounded)
+}
+
+/// The box common to both `a` and `b`, if they overlap at all.
+fn overlap(a: &Bounds, b: &Bounds) -> Option<Bounds> {
+    let min = a.0.sup(&b.0);
+    let max = a.1.inf(&b.1);
+    if min.x < max.x && min.y < max.y && min.z < max.z {
+        Some((min, max))
+    } else {
+        None
+    }
+}
+
+/// Volume held by both `first` and `second`. This is exactly 0 if their bounding boxes do not
+/// overlap, and exact if both are spheres. Otherwise it is estimated by `integrator` over the
+/// overlap of the two boxes.
+fn shared_volume<A: Container, B: Container>(
+    first: &A,
+    second: &B,
+    integrator: Integrator,
+) -> Result<f32, Error> {
+    if let (Some(a), Some(b)) = (first.as_sphere(), second.as_sphere()) {
+        return Ok(lens_volume(&a, &b));
+    }
+    Ok(match overlap(&bounds(first)?, &bounds(second)?) {
+        Some((min, max)) => integrator.volume(&min, &max, |point| {
+            let point = Sphere {
+                center: *point,
+                radius: 0.,
+            };
+            first.contains(&point) && second.contains(&point)
+        })?,
+        None => 0.,
+    })
+}
+
+/// Volume of the lens shared by spheres `a` and `b`.
+fn lens_volume(a: &Sphere, b: &Sphere) -> f32 {
+    let d = nalgebra::distance(&a.center, &b.center);
+    let (sum, difference) = (a.radius + b.radius, a.radius - b.radius);
+    if d >= sum {
+        0.
+    } else if d <= difference.abs() {
+        // The smaller sphere lies wholly inside the larger
+        4. * FRAC_PI_3 * a.radius.min(b.radius).powi(3)
+    } else {
+        PI * (sum - d).powi(2) * (d.powi(2) + 2. * d * sum - 3. * difference.powi(2)) / (12. * d)
+    }
+}
+
+/// Whether every point of `sphere` within the cube centered on `center` with half side `half`
+/// lies inside `first` or `second`. The cube is tested by the ball circumscribing it, and split
+/// into octants up to `depth` times where that ball lies inside neither container.
+fn cell_covered<A: Container, B: Container>(
+    first: &A,
+    second: &B,
+    sphere: &Sphere,
+    center: Point3<f32>,
+    half: f32,
+    depth: usize,
+) -> bool {
+    let reach = half * 3f32.sqrt();
+    if nalgebra::distance(&center, &sphere.center) >= sphere.radius + reach {
+        // Only a corner of the sphere's bounding cube, outside of the sphere itself
+        return true;
+    }
+    let ball = Sphere {
+        center,
+        radius: reach,
+    };
+    if first.contains(&ball) || second.contains(&ball) {
+        true
+    } else if depth == 0 {
+        false
+    } else {
+        octants(center, half / 2.)
+            .all(|octant| cell_covered(first, second, sphere, octant, half / 2., depth - 1))
+    }
+}
+
+/// Centers of the eight cubes with half side `half` which fill the cube centered on `center`.
+fn octants(center: Point3<f32>, half: f32) -> impl Iterator<Item = Point3<f32>> {
+    (0..8).map(move |idx| {
+        let sign = |bit: usize| if idx & bit == 0 { -half } else { half };
+        center + Vector3::new(sign(1), sign(2), sign(4))
+    })
+}
+
+#[derive(PartialEq, Debug, Clone)]
+/// The region inside either of two containers.
+///
+/// Where `first` and `second` overlap, a sphere may lie within the union while crossing the
+/// surface of one inside the other. Such spheres are found by covering them with smaller balls,
+/// each of which must lie wholly within one of the two containers, so no sphere is ever placed
+/// outside of the union. Spheres which cross the seam close to where it meets the outer surface
+/// of the union may still be rejected.
+///
+/// The volume of the union is exact when the bounding boxes of the two containers are disjoint,
+/// or when both are spheres. Otherwise the volume they share is estimated. By default
+/// `VOLUME_SAMPLES` points are sampled over the box common to both bounding boxes, giving a
+/// standard error of at most `0.5 / sqrt(VOLUME_SAMPLES)`, about 0.1%, of that box's volume.
+/// A grid `Integrator` errs by at most the volume of the cells a surface passes through.
+pub struct Union<A, B> {
+    first: A,
+    second: B,
+    volume: f32,
+}
+
+impl<A: Container, B: Container> Union<A, B> {
+    /// Combines `first` and `second`. Both must report a bounding box, from which the volume of
+    /// the union is found: exactly if the boxes are disjoint or both containers are spheres,
+    /// and by Monte Carlo estimation of the volume shared by both containers otherwise.
+    /// `csg::VOLUME_SAMPLES` points are sampled.
+    pub fn new(first: A, second: B) -> Result<Union<A, B>, Error> {
+        Union::with_integrator(first, second, DEFAULT_INTEGRATOR)
+    }
+
+    /// As with `new`, but where the volume has to be estimated it is found with `integrator`,
+    /// over the box common to both bounding boxes.
+    pub fn with_integrator(
+        first: A,
+        second: B,
+        integrator: Integrator,
+    ) -> Result<Union<A, B>, Error> {
+        let volume = first.volume() + second.volume() - shared_volume(&first, &second, integrator)?;
+        Ok(Union {
+            first,
+            second,
+            volume,
+        })
+    }
+}
+
+impl<A, B> Union<A, B> {
+    /// The first of the combined containers.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The second of the combined containers.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Container, B: Container> Container for Union<A, B> {
+    /// Checks if sphere exists entirely inside the union. Spheres inside neither container
+    /// alone are accepted if their center lies within one of them and each octant of the sphere
+    /// can be covered by balls lying within either.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        if self.first.contains(sphere) || self.second.contains(sphere) {
+            return true;
+        }
+        let center = Sphere {
+            center: sphere.center,
+            radius: 0.,
+        };
+        if !self.first.contains(&center) && !self.second.contains(&center) {
+            return false;
+        }
+        let half = sphere.radius / 2.;
+        octants(sphere.center, half)
+            .all(|octant| cell_covered(&self.first, &self.second, sphere, octant, half, SEAM_DEPTH))
+    }
+
+    /// The volume of the union, found on construction.
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Packing starts from the seed point of the first container.
+    fn seed_point(&self) -> Point3<f32> {
+        self.first.seed_point()
+    }
+
+    /// The box enclosing the bounding boxes of both containers.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let (first_min, first_max) = self.first.bounding_box()?;
+        let (second_min, second_max) = self.second.bounding_box()?;
+        Some((first_min.inf(&second_min), first_max.sup(&second_max)))
+    }
+}
+
+impl<A: Disjoint, B: Disjoint> Disjoint for Union<A, B> {
+    /// Checks if sphere lies outside both containers.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.first.is_disjoint(sphere) && self.second.is_disjoint(sphere)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+/// The region inside both of two containers.
+///
+/// Its volume is exactly 0 when the bounding boxes of the two containers are disjoint, and exact
+/// when both are spheres. Otherwise it is estimated, and the estimate errs as described for
+/// [Union](struct.Union.html): relative to the intersection itself, the error is largest when
+/// the intersection fills little of the box common to both bounding boxes.
+pub struct Intersection<A, B> {
+    first: A,
+    second: B,
+    volume: f32,
+}
+
+impl<A: Container, B: Container> Intersection<A, B> {
+    /// Combines `first` and `second`. Both must report a bounding box, from which the volume of
+    /// the intersection is found: exactly 0 if the boxes are disjoint, exactly if both
+    /// containers are spheres, and by Monte Carlo estimation otherwise.
+    /// `csg::VOLUME_SAMPLES` points are sampled.
+    pub fn new(first: A, second: B) -> Result<Intersection<A, B>, Error> {
+        Intersection::with_integrator(first, second, DEFAULT_INTEGRATOR)
+    }
+
+    /// As with `new`, but where the volume has to be estimated it is found with `integrator`,
+    /// over the box common to both bounding boxes.
+    pub fn with_integrator(
+        first: A,
+        second: B,
+        integrator: Integrator,
+    ) -> Result<Intersection<A, B>, Error> {
+        let volume = shared_volume(&first, &second, integrator)?;
+        Ok(Intersection {
+            first,
+            second,
+            volume,
+        })
+    }
+}
+
+impl<A, B> Intersection<A, B> {
+    /// The first of the combined containers.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The second of the combined containers.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Container, B: Container> Container for Intersection<A, B> {
+    /// Checks if sphere exists entirely inside both containers.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.first.contains(sphere) && self.second.contains(sphere)
+    }
+
+    /// The volume of the intersection, found on construction.
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Packing starts from the seed point of the first container. If the initial spheres do
+    /// not fit there, the bounding box is searched instead.
+    fn seed_point(&self) -> Point3<f32> {
+        self.first.seed_point()
+    }
+
+    /// The box common to the bounding boxes of both containers.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        overlap(&self.first.bounding_box()?, &self.second.bounding_box()?)
+    }
+}
+
+impl<A: Disjoint, B: Disjoint> Disjoint for Intersection<A, B> {
+    /// Checks if sphere lies outside either container. Spheres reaching into both containers
+    /// but not their intersection are conservatively reported as not disjoint.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.first.is_disjoint(sphere) || self.second.is_disjoint(sphere)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+/// The region inside one container once another has been cut away from it.
+///
+/// Its volume is exactly that of `first` when the bounding boxes of the two containers are
+/// disjoint, and exact when both are spheres. Otherwise the volume cut away is estimated, with
+/// the same error as the volume shared by the two containers of a [Union](struct.Union.html).
+pub struct Difference<A, B> {
+    first: A,
+    second: B,
+    volume: f32,
+}
+
+impl<A: Container, B: Container + Disjoint> Difference<A, B> {
+    /// Cuts `second` away from `first`. Both must report a bounding box, from which the volume
+    /// of the difference is found: exactly if the boxes are disjoint or both containers are
+    /// spheres, and by Monte Carlo estimation of the volume removed from `first` otherwise.
+    /// `csg::VOLUME_SAMPLES` points are sampled.
+    pub fn new(first: A, second: B) -> Result<Difference<A, B>, Error> {
+        Difference::with_integrator(first, second, DEFAULT_INTEGRATOR)
+    }
+
+    /// As with `new`, but where the volume has to be estimated it is found with `integrator`,
+    /// over the box common to both bounding boxes.
+    pub fn with_integrator(
+        first: A,
+        second: B,
+        integrator: Integrator,
+    ) -> Result<Difference<A, B>, Error> {
+        let volume = first.volume() - shared_volume(&first, &second, integrator)?;
+        Ok(Difference {
+            first,
+            second,
+            volume,
+        })
+    }
+}
+
+impl<A, B> Difference<A, B> {
+    /// The container which is cut from.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The container which is cut away.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Container, B: Container + Disjoint> Container for Difference<A, B> {
+    /// Checks if sphere exists entirely inside the first container without reaching into
+    /// the second.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.first.contains(sphere) && self.second.is_disjoint(sphere)
+    }
+
+    /// The volume of the difference, found on construction.
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Packing starts from the seed point of the first container. If that has been cut away,
+    /// the bounding box is searched instead.
+    fn seed_point(&self) -> Point3<f32> {
+        self.first.seed_point()
+    }
+
+    /// The bounding box of the first container.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        self.first.bounding_box()
+    }
+}
+
+impl<A: Disjoint, B: Container> Disjoint for Difference<A, B> {
+    /// Checks if sphere lies outside the first container, or entirely within the part cut away.
+    /// Spheres reaching into both the cut and the remainder, but not the difference itself,
+    /// are conservatively reported as not disjoint.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.first.is_disjoint(sphere) || self.second.contains(sphere)
+    }
+}
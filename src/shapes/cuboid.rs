@@ -1,45 +1,65 @@
 use crate::errors::SphericalCowError as Error;
 use crate::shapes::Sphere;
-use crate::Container;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3, Vector3};
 
 #[derive(PartialEq, Debug, Clone)]
-/// Constructs a cuboid centered at the origin in Euclidean space.
+/// Constructs a cuboid in Euclidean space. By default the cuboid is axis aligned and centered
+/// at the origin, but it may be moved and rotated arbitrarily with an `isometry`.
 pub struct Cuboid {
     /// Central point in space where this sphere is located.
     pub half_extents: Vec<f32>,
+    /// Rigid transform taking the cuboid from its axis aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
 }
 
 impl Cuboid {
     /// Creates a new box from its `half_extents`. Half-extents are the box half-width along each
     /// axis, all of which must be greater than 0.
     pub fn new(extent_x: f32, extent_y: f32, extent_z: f32) -> Result<Cuboid, Error> {
-        //TODO: Should also have an orientation vector so this can be arbitrarily rotated too.
         if extent_x <= 0.0 || extent_y <= 0.0 || extent_z <= 0.0 {
             Err(Error::NegativeExtents)
         } else {
             Ok(Cuboid {
                 half_extents: vec![extent_x, extent_y, extent_z],
+                isometry: Isometry3::identity(),
             })
         }
     }
 
-    /// Similar than calling `new`, but the `half_extents` are contained within a vector.
+    /// Similar than calling `new`, but the `half_extents` are contained within a vector, which
+    /// must hold exactly three of them.
     pub fn from_vec(half_extents: Vec<f32>) -> Result<Cuboid, Error> {
-        if half_extents.iter().any(|he| *he <= 0.0) {
+        if half_extents.len() != 3 {
+            Err(Error::ExtentCount)
+        } else if half_extents.iter().any(|he| *he <= 0.0) {
             Err(Error::NegativeExtents)
         } else {
-            Ok(Cuboid { half_extents })
+            Ok(Cuboid {
+                half_extents,
+                isometry: Isometry3::identity(),
+            })
         }
     }
+
+    /// Places this cuboid into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Cuboid {
+        self.isometry = isometry;
+        self
+    }
 }
 
 impl Container for Cuboid {
     /// Checks if sphere exists inside the current bounding box.
     fn contains(&self, sphere: &Sphere) -> bool {
-        // Sphere's center point plus its radius must be less than all three half_extents
+        // Sphere's center point plus its radius must be less than all three half_extents,
+        // once taken into the cuboid's own frame
+        let center = self.isometry.inverse_transform_point(&sphere.center);
         self.half_extents
             .iter()
-            .zip(sphere.center.coords.iter())
+            .zip(center.coords.iter())
             .all(|(extent, sphere_extent)| sphere_extent.abs() + sphere.radius <= *extent)
     }
 
@@ -50,4 +70,37 @@ impl Container for Cuboid {
             .map(|half_extent| 2. * half_extent)
             .product()
     }
+
+    /// Packing starts from the center of this cuboid.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::from(self.isometry.translation.vector)
+    }
+
+    /// The axis aligned box enclosing this cuboid, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let half_extents = Vector3::new(
+            self.half_extents[0],
+            self.half_extents[1],
+            self.half_extents[2],
+        );
+        let rotation = self.isometry.rotation.to_rotation_matrix();
+        let reach = rotation.matrix().abs() * half_extents;
+        let center = self.seed_point();
+        Some((center - reach, center + reach))
+    }
+}
+
+impl Disjoint for Cuboid {
+    /// Checks if sphere lies completely outside the current cuboid.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        // Distance from the sphere's center to the nearest point of the box
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        let gap: f32 = self
+            .half_extents
+            .iter()
+            .zip(center.coords.iter())
+            .map(|(extent, sphere_extent)| (sphere_extent.abs() - extent).max(0.).powi(2))
+            .sum();
+        gap.sqrt() >= sphere.radius
+    }
 }
@@ -0,0 +1,83 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3, Vector3};
+use std::f32::consts::PI;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs a right circular cylinder in Euclidean space. By default the cylinder's axis is
+/// aligned with z and its center sits at the origin, but it may be moved and rotated
+/// arbitrarily with an `isometry`.
+pub struct Cylinder {
+    /// Radius of the circular cross section.
+    pub radius: f32,
+    /// Full length of the cylinder along its axis.
+    pub height: f32,
+    /// Rigid transform taking the cylinder from its z aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl Cylinder {
+    /// Creates a new cylinder from its `radius` and `height`, both of which must be greater than 0.
+    pub fn new(radius: f32, height: f32) -> Result<Cylinder, Error> {
+        if radius <= 0.0 {
+            Err(Error::NegativeRadius)
+        } else if height <= 0.0 {
+            Err(Error::NegativeHeight)
+        } else {
+            Ok(Cylinder {
+                radius,
+                height,
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this cylinder into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Cylinder {
+        self.isometry = isometry;
+        self
+    }
+}
+
+impl Container for Cylinder {
+    /// Checks if sphere exists inside the current cylinder.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        // In the cylinder's own frame, the sphere must clear both the curved wall and the end caps
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        let axial = center.x.hypot(center.y);
+        axial + sphere.radius <= self.radius && center.z.abs() + sphere.radius <= 0.5 * self.height
+    }
+
+    /// Calculates the volume of this cylinder in normalised units.
+    fn volume(&self) -> f32 {
+        PI * self.radius.powi(2) * self.height
+    }
+
+    /// Packing starts from the center of this cylinder.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::from(self.isometry.translation.vector)
+    }
+
+    /// The axis aligned box enclosing this cylinder, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let half_extents = Vector3::new(self.radius, self.radius, 0.5 * self.height);
+        let rotation = self.isometry.rotation.to_rotation_matrix();
+        let reach = rotation.matrix().abs() * half_extents;
+        let center = self.seed_point();
+        Some((center - reach, center + reach))
+    }
+}
+
+impl Disjoint for Cylinder {
+    /// Checks if sphere lies completely outside the current cylinder.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        // Distance from the sphere's center to the cylinder's rectangular cross section
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        let radial = (center.x.hypot(center.y) - self.radius).max(0.);
+        let axial = (center.z.abs() - 0.5 * self.height).max(0.);
+        radial.hypot(axial) >= sphere.radius
+    }
+}
@@ -0,0 +1,101 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3, Vector3};
+use std::f32::consts::PI;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs the hollow region between two coaxial cylinders of equal height, such as the wall
+/// of a pipe. By default the shell's axis is aligned with z and its center sits at the origin,
+/// but it may be moved and rotated arbitrarily with an `isometry`.
+pub struct CylindricalShell {
+    /// Radius of the hollow core.
+    pub inner_radius: f32,
+    /// Radius of the outer surface.
+    pub outer_radius: f32,
+    /// Full length of the shell along its axis.
+    pub height: f32,
+    /// Rigid transform taking the shell from its z aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl CylindricalShell {
+    /// Creates a new shell from its radii and `height`. The `inner_radius` may be 0 but not
+    /// negative, and must be smaller than the `outer_radius`. The `height` must be greater than 0.
+    pub fn new(
+        inner_radius: f32,
+        outer_radius: f32,
+        height: f32,
+    ) -> Result<CylindricalShell, Error> {
+        if inner_radius < 0.0 {
+            Err(Error::NegativeRadius)
+        } else if inner_radius >= outer_radius {
+            Err(Error::InvertedShell)
+        } else if height <= 0.0 {
+            Err(Error::NegativeHeight)
+        } else {
+            Ok(CylindricalShell {
+                inner_radius,
+                outer_radius,
+                height,
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this shell into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> CylindricalShell {
+        self.isometry = isometry;
+        self
+    }
+}
+
+impl Container for CylindricalShell {
+    /// Checks if sphere lies outside the inner surface, inside the outer one and between
+    /// both ends of the shell.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        let axial = center.x.hypot(center.y);
+        axial - sphere.radius >= self.inner_radius
+            && axial + sphere.radius <= self.outer_radius
+            && center.z.abs() + sphere.radius <= 0.5 * self.height
+    }
+
+    /// Calculates the volume of this shell in normalised units.
+    fn volume(&self) -> f32 {
+        PI * (self.outer_radius.powi(2) - self.inner_radius.powi(2)) * self.height
+    }
+
+    /// Packing starts midway through the wall of the shell, along local x.
+    fn seed_point(&self) -> Point3<f32> {
+        let mid_wall = 0.5 * (self.inner_radius + self.outer_radius);
+        self.isometry
+            .transform_point(&Point3::new(mid_wall, 0., 0.))
+    }
+
+    /// The axis aligned box enclosing this shell, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let half_extents = Vector3::new(self.outer_radius, self.outer_radius, 0.5 * self.height);
+        let rotation = self.isometry.rotation.to_rotation_matrix();
+        let reach = rotation.matrix().abs() * half_extents;
+        let center = Point3::from(self.isometry.translation.vector);
+        Some((center - reach, center + reach))
+    }
+}
+
+impl Disjoint for CylindricalShell {
+    /// Checks if sphere lies completely outside the wall of the current shell, including
+    /// within its hollow core.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        // Distance from the sphere's center to the wall's rectangular cross section
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        let axial_distance = center.x.hypot(center.y);
+        let radial = (self.inner_radius - axial_distance)
+            .max(axial_distance - self.outer_radius)
+            .max(0.);
+        let axial = (center.z.abs() - 0.5 * self.height).max(0.);
+        radial.hypot(axial) >= sphere.radius
+    }
+}
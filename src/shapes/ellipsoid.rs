@@ -0,0 +1,239 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3, Vector3};
+use std::cmp::Ordering;
+use std::f32::consts::FRAC_PI_3;
+
+/// Enough bisection steps to exhaust the precision of an `f64`.
+const MAX_ITERATIONS: usize = 1100;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs an ellipsoid in Euclidean space. By default the ellipsoid's axes are aligned with
+/// x, y and z and its center sits at the origin, but it may be moved and rotated arbitrarily with
+/// an `isometry`.
+pub struct Ellipsoid {
+    /// Half length of each of the ellipsoid's principal axes, along local x, y and z.
+    pub semi_axes: Vector3<f32>,
+    /// Rigid transform taking the ellipsoid from its axis aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl Ellipsoid {
+    /// Creates a new ellipsoid from its semi-axes, all of which must be greater than 0.
+    pub fn new(semi_x: f32, semi_y: f32, semi_z: f32) -> Result<Ellipsoid, Error> {
+        if semi_x <= 0.0 || semi_y <= 0.0 || semi_z <= 0.0 {
+            Err(Error::NegativeExtents)
+        } else {
+            Ok(Ellipsoid {
+                semi_axes: Vector3::new(semi_x, semi_y, semi_z),
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this ellipsoid into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Ellipsoid {
+        self.isometry = isometry;
+        self
+    }
+
+    /// Whether `point` lies inside this ellipsoid, along with its distance to the surface.
+    /// `semi_axes` may have been set directly, so if they do not describe an ellipsoid the
+    /// distance is NaN, which contains nothing and is disjoint from nothing.
+    fn surface_gap(&self, point: &Point3<f32>) -> (bool, f64) {
+        if !self
+            .semi_axes
+            .iter()
+            .all(|axis| axis.is_finite() && *axis > 0.)
+        {
+            return (false, f64::NAN);
+        }
+        let local = self.isometry.inverse_transform_point(point);
+        let level: f32 = local
+            .coords
+            .component_div(&self.semi_axes)
+            .iter()
+            .map(|c| c.powi(2))
+            .sum();
+        let semi_axes = [
+            f64::from(self.semi_axes.x),
+            f64::from(self.semi_axes.y),
+            f64::from(self.semi_axes.z),
+        ];
+        let local = [f64::from(local.x), f64::from(local.y), f64::from(local.z)];
+        (level <= 1., surface_distance(semi_axes, local))
+    }
+}
+
+impl Container for Ellipsoid {
+    /// Checks if sphere exists inside the current ellipsoid.
+    ///
+    /// A sphere scaled along with the ellipsoid is no longer a sphere, so the sphere's center
+    /// must instead be found to lie inside, and at least one radius from the surface.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        let (inside, gap) = self.surface_gap(&sphere.center);
+        inside && gap >= f64::from(sphere.radius)
+    }
+
+    /// Calculates the volume of this ellipsoid in normalised units.
+    fn volume(&self) -> f32 {
+        4. * FRAC_PI_3 * self.semi_axes.x * self.semi_axes.y * self.semi_axes.z
+    }
+
+    /// Packing starts from the center of this ellipsoid.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::from(self.isometry.translation.vector)
+    }
+
+    /// The tightest axis aligned box enclosing this ellipsoid, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let rotation = self.isometry.rotation.to_rotation_matrix();
+        // Each row holds the world axis expressed against the scaled local axes
+        let scaled = rotation.matrix() * nalgebra::Matrix3::from_diagonal(&self.semi_axes);
+        let reach = Vector3::from_iterator(scaled.row_iter().map(|row| row.norm()));
+        let center = self.seed_point();
+        Some((center - reach, center + reach))
+    }
+}
+
+impl Disjoint for Ellipsoid {
+    /// Checks if sphere lies completely outside the current ellipsoid.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        let (inside, gap) = self.surface_gap(&sphere.center);
+        !inside && gap >= f64::from(sphere.radius)
+    }
+}
+
+/// Shortest distance from `point` to the surface of an axis aligned ellipsoid with the given
+/// `semi_axes`, following D. Eberly, *Distance from a Point to an Ellipse, an Ellipsoid, or a
+/// Hyperellipsoid* (2013). The problem is reduced by symmetry to the first octant with the axes
+/// in decreasing order, then the single root of a monotonic function is bisected, which is robust
+/// where Newton's method is not.
+fn surface_distance(semi_axes: [f64; 3], point: [f64; 3]) -> f64 {
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| {
+        semi_axes[b]
+            .partial_cmp(&semi_axes[a])
+            .unwrap_or(Ordering::Equal)
+    });
+    let e = [
+        semi_axes[order[0]],
+        semi_axes[order[1]],
+        semi_axes[order[2]],
+    ];
+    let y = [
+        point[order[0]].abs(),
+        point[order[1]].abs(),
+        point[order[2]].abs(),
+    ];
+
+    if y[2] > 0. {
+        if y[1] > 0. {
+            if y[0] > 0. {
+                let z = [y[0] / e[0], y[1] / e[1], y[2] / e[2]];
+                let g = z[0].powi(2) + z[1].powi(2) + z[2].powi(2) - 1.;
+                if g == 0. {
+                    return 0.;
+                }
+                let r = [(e[0] / e[2]).powi(2), (e[1] / e[2]).powi(2), 1.];
+                let s = ellipsoid_root(r[0], r[1], z, g);
+                (0..3)
+                    .map(|i| (r[i] * y[i] / (s + r[i]) - y[i]).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            } else {
+                ellipse_distance([e[1], e[2]], [y[1], y[2]])
+            }
+        } else if y[0] > 0. {
+            ellipse_distance([e[0], e[2]], [y[0], y[2]])
+        } else {
+            (y[2] - e[2]).abs()
+        }
+    } else {
+        // On the plane of the two largest axes, the nearest point may still leave that plane
+        let denominator = [e[0].powi(2) - e[2].powi(2), e[1].powi(2) - e[2].powi(2)];
+        let numerator = [e[0] * y[0], e[1] * y[1]];
+        if numerator[0] < denominator[0] && numerator[1] < denominator[1] {
+            let xde = [numerator[0] / denominator[0], numerator[1] / denominator[1]];
+            let discriminant = 1. - xde[0].powi(2) - xde[1].powi(2);
+            if discriminant > 0. {
+                let x = [e[0] * xde[0], e[1] * xde[1], e[2] * discriminant.sqrt()];
+                return ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + x[2].powi(2)).sqrt();
+            }
+        }
+        ellipse_distance([e[0], e[1]], [y[0], y[1]])
+    }
+}
+
+/// The two dimensional counterpart of `surface_distance`, for
+/// `semi_axes` in decreasing order and a `point` in the first quadrant.
+fn ellipse_distance(e: [f64; 2], y: [f64; 2]) -> f64 {
+    if y[1] > 0. {
+        if y[0] > 0. {
+            let z = [y[0] / e[0], y[1] / e[1]];
+            let g = z[0].powi(2) + z[1].powi(2) - 1.;
+            if g == 0. {
+                return 0.;
+            }
+            let r = (e[0] / e[1]).powi(2);
+            let s = ellipse_root(r, z, g);
+            let x = [r * y[0] / (s + r), y[1] / (s + 1.)];
+            ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2)).sqrt()
+        } else {
+            (y[1] - e[1]).abs()
+        }
+    } else {
+        let numerator = e[0] * y[0];
+        let denominator = e[0].powi(2) - e[1].powi(2);
+        if numerator < denominator {
+            let xde = numerator / denominator;
+            let x = [e[0] * xde, e[1] * (1. - xde.powi(2)).sqrt()];
+            ((x[0] - y[0]).powi(2) + x[1].powi(2)).sqrt()
+        } else {
+            (y[0] - e[0]).abs()
+        }
+    }
+}
+
+/// Bisects for the root of `(r₀z₀/(s+r₀))² + (z₁/(s+1))² - 1`.
+fn ellipse_root(r: f64, z: [f64; 2], g: f64) -> f64 {
+    let n = r * z[0];
+    bisect(z[1] - 1., g, n.hypot(z[1]) - 1., |s| {
+        (n / (s + r)).powi(2) + (z[1] / (s + 1.)).powi(2) - 1.
+    })
+}
+
+/// Bisects for the root of `(r₀z₀/(s+r₀))² + (r₁z₁/(s+r₁))² + (z₂/(s+1))² - 1`.
+fn ellipsoid_root(r_0: f64, r_1: f64, z: [f64; 3], g: f64) -> f64 {
+    let n = [r_0 * z[0], r_1 * z[1]];
+    let length = (n[0].powi(2) + n[1].powi(2) + z[2].powi(2)).sqrt();
+    bisect(z[2] - 1., g, length - 1., |s| {
+        (n[0] / (s + r_0)).powi(2) + (n[1] / (s + r_1)).powi(2) + (z[2] / (s + 1.)).powi(2) - 1.
+    })
+}
+
+/// Bisection on a decreasing `function` between `lower` and, for points outside the surface
+/// (`g > 0`), `upper`. Points inside have their root between `lower` and 0.
+fn bisect<F: Fn(f64) -> f64>(lower: f64, g: f64, upper: f64, function: F) -> f64 {
+    let mut s_0 = lower;
+    let mut s_1 = if g < 0. { 0. } else { upper };
+    let mut s = 0.;
+    for _ in 0..MAX_ITERATIONS {
+        s = 0.5 * (s_0 + s_1);
+        if s == s_0 || s == s_1 {
+            break;
+        }
+        let value = function(s);
+        if value > 0. {
+            s_0 = s;
+        } else if value < 0. {
+            s_1 = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
@@ -0,0 +1,177 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point2, Point3, Vector3};
+use std::f32::consts::FRAC_PI_3;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs a frustum (a cone with its tip cut off parallel to its base) in Euclidean space.
+/// By default the frustum's axis is aligned with z and its center sits at the origin, with the
+/// `bottom_radius` face at `-height / 2`. It may be moved and rotated arbitrarily with an `isometry`.
+pub struct Frustum {
+    /// Radius of the circular face at the bottom of the frustum.
+    pub bottom_radius: f32,
+    /// Radius of the circular face at the top of the frustum.
+    pub top_radius: f32,
+    /// Distance between the two circular faces.
+    pub height: f32,
+    /// Rigid transform taking the frustum from its z aligned, origin centered frame into
+    /// world coordinates.
+    pub isometry: Isometry3<f32>,
+}
+
+impl Frustum {
+    /// Creates a new frustum from the radii of its `bottom` and `top` faces and its `height`,
+    /// all of which must be greater than 0.
+    pub fn new(bottom_radius: f32, top_radius: f32, height: f32) -> Result<Frustum, Error> {
+        if bottom_radius <= 0.0 || top_radius <= 0.0 {
+            Err(Error::NegativeRadius)
+        } else if height <= 0.0 {
+            Err(Error::NegativeHeight)
+        } else {
+            Ok(Frustum {
+                bottom_radius,
+                top_radius,
+                height,
+                isometry: Isometry3::identity(),
+            })
+        }
+    }
+
+    /// Places this frustum into world coordinates: it is rotated about its center, then
+    /// translated according to `isometry`.
+    pub fn with_isometry(mut self, isometry: Isometry3<f32>) -> Frustum {
+        self.isometry = isometry;
+        self
+    }
+}
+
+impl Container for Frustum {
+    /// Checks if sphere exists inside the current frustum.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        in_truncated_cone(
+            self.bottom_radius,
+            self.top_radius,
+            self.height,
+            &center,
+            sphere.radius,
+        )
+    }
+
+    /// Calculates the volume of this frustum in normalised units.
+    fn volume(&self) -> f32 {
+        FRAC_PI_3
+            * self.height
+            * (self.bottom_radius.powi(2)
+                + self.bottom_radius * self.top_radius
+                + self.top_radius.powi(2))
+    }
+
+    /// Packing starts from the center of this frustum.
+    fn seed_point(&self) -> Point3<f32> {
+        Point3::from(self.isometry.translation.vector)
+    }
+
+    /// The axis aligned box enclosing this frustum, whatever its orientation.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        truncated_cone_bounds(
+            self.bottom_radius.max(self.top_radius),
+            self.height,
+            &self.isometry,
+        )
+    }
+}
+
+/// Checks if a sphere at `center` (in local coordinates) with `radius` lies within a z aligned,
+/// origin centered truncated cone. `top` may be 0, giving a cone.
+///
+/// Both shapes are solids of revolution, so the distance from the sphere's center to their
+/// surface is the distance within the meridian half plane (radial distance, z) from the
+/// center to the trapezoid's base, slanted side and top. The axis itself is not a boundary.
+pub(crate) fn in_truncated_cone(
+    bottom: f32,
+    top: f32,
+    height: f32,
+    center: &Point3<f32>,
+    radius: f32,
+) -> bool {
+    let half_height = 0.5 * height;
+    let point = Point2::new(center.x.hypot(center.y), center.z);
+    // Center must first be inside the trapezoid
+    let fraction = (point.y + half_height) / height;
+    if point.y.abs() > half_height || point.x > bottom + (top - bottom) * fraction {
+        return false;
+    }
+    let base = (
+        Point2::new(0., -half_height),
+        Point2::new(bottom, -half_height),
+    );
+    let side = (
+        Point2::new(bottom, -half_height),
+        Point2::new(top, half_height),
+    );
+    let lid = (Point2::new(top, half_height), Point2::new(0., half_height));
+    [base, side, lid]
+        .iter()
+        .all(|(from, to)| segment_distance(&point, from, to) >= radius)
+}
+
+/// Distance from `center` (in local coordinates) to the nearest point of a z aligned, origin
+/// centered truncated cone, or 0 if `center` lies inside. As with
+/// [in_truncated_cone](fn.in_truncated_cone.html), this is a distance within the meridian half plane.
+pub(crate) fn truncated_cone_gap(bottom: f32, top: f32, height: f32, center: &Point3<f32>) -> f32 {
+    let half_height = 0.5 * height;
+    let point = Point2::new(center.x.hypot(center.y), center.z);
+    let fraction = (point.y + half_height) / height;
+    if point.y.abs() <= half_height && point.x <= bottom + (top - bottom) * fraction {
+        return 0.;
+    }
+    let base = (
+        Point2::new(0., -half_height),
+        Point2::new(bottom, -half_height),
+    );
+    let side = (
+        Point2::new(bottom, -half_height),
+        Point2::new(top, half_height),
+    );
+    let lid = (Point2::new(top, half_height), Point2::new(0., half_height));
+    [base, side, lid]
+        .iter()
+        .map(|(from, to)| segment_distance(&point, from, to))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// The axis aligned box enclosing a z aligned, origin centered cone or frustum with a widest
+/// radius of `radius`, once moved into world coordinates by `isometry`.
+pub(crate) fn truncated_cone_bounds(
+    radius: f32,
+    height: f32,
+    isometry: &Isometry3<f32>,
+) -> Option<(Point3<f32>, Point3<f32>)> {
+    let half_extents = Vector3::new(radius, radius, 0.5 * height);
+    let rotation = isometry.rotation.to_rotation_matrix();
+    let reach = rotation.matrix().abs() * half_extents;
+    let center = Point3::from(isometry.translation.vector);
+    Some((center - reach, center + reach))
+}
+
+/// Shortest distance from `point` to the line segment between `from` and `to`.
+fn segment_distance(point: &Point2<f32>, from: &Point2<f32>, to: &Point2<f32>) -> f32 {
+    let edge = to - from;
+    let length_2 = edge.norm_squared();
+    if length_2 == 0. {
+        return nalgebra::distance(point, from);
+    }
+    let t = ((point - from).dot(&edge) / length_2).clamp(0., 1.);
+    nalgebra::distance(point, &(from + edge * t))
+}
+
+impl Disjoint for Frustum {
+    /// Checks if sphere lies completely outside the current frustum.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        let center = self.isometry.inverse_transform_point(&sphere.center);
+        truncated_cone_gap(self.bottom_radius, self.top_radius, self.height, &center)
+            >= sphere.radius
+    }
+}
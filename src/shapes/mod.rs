@@ -1,7 +1,31 @@
 //! Collection of shapes usefull as containers, along with the all important `Sphere`.
 
+pub use self::capsule::Capsule;
+pub use self::cone::Cone;
+pub use self::csg::{Difference, Intersection, Union};
 pub use self::cuboid::Cuboid;
+pub use self::cylinder::Cylinder;
+pub use self::cylindrical_shell::CylindricalShell;
+pub use self::ellipsoid::Ellipsoid;
+pub use self::frustum::Frustum;
+pub use self::periodic_cuboid::PeriodicCuboid;
+pub use self::sdf::{Integrator, SdfContainer};
 pub use self::sphere::Sphere;
+pub use self::spherical_shell::SphericalShell;
+pub use self::transformed::Transformed;
+pub use self::trimesh::TriMesh;
 
+mod capsule;
+mod cone;
+pub mod csg;
 mod cuboid;
+mod cylinder;
+mod cylindrical_shell;
+mod ellipsoid;
+mod frustum;
+mod periodic_cuboid;
+mod sdf;
 mod sphere;
+mod spherical_shell;
+mod transformed;
+mod trimesh;
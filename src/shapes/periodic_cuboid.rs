@@ -0,0 +1,62 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::Container;
+use nalgebra::Vector3;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs a periodic cuboid centered at the origin in Euclidean space. Packing into this
+/// container yields a representative volume element which tiles space: spheres may cross
+/// any face and wrap around to the opposite side.
+///
+/// The minimum image convention is used when checking for overlaps, so each half extent
+/// should be several times larger than the largest sphere radius you intend to pack.
+pub struct PeriodicCuboid {
+    /// Half-widths of the periodic cell along each axis.
+    pub half_extents: Vec<f32>,
+}
+
+impl PeriodicCuboid {
+    /// Creates a new periodic box from its `half_extents`. Half-extents are the box half-width
+    /// along each axis, all of which must be greater than 0.
+    pub fn new(extent_x: f32, extent_y: f32, extent_z: f32) -> Result<PeriodicCuboid, Error> {
+        PeriodicCuboid::from_vec(vec![extent_x, extent_y, extent_z])
+    }
+
+    /// Similar than calling `new`, but the `half_extents` are contained within a vector, which
+    /// must hold exactly three of them.
+    pub fn from_vec(half_extents: Vec<f32>) -> Result<PeriodicCuboid, Error> {
+        if half_extents.len() != 3 {
+            Err(Error::ExtentCount)
+        } else if half_extents.iter().any(|he| *he <= 0.0) {
+            Err(Error::NegativeExtents)
+        } else {
+            Ok(PeriodicCuboid { half_extents })
+        }
+    }
+}
+
+impl Container for PeriodicCuboid {
+    /// Checks if the sphere's center lies within the periodic cell. The sphere itself
+    /// is free to cross the cell's faces, but may not be larger than the cell.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.half_extents
+            .iter()
+            .zip(sphere.center.coords.iter())
+            .all(|(extent, center)| center.abs() <= *extent && sphere.radius <= *extent)
+    }
+
+    /// Calculates the volume of the periodic cell in normalised units.
+    fn volume(&self) -> f32 {
+        self.half_extents
+            .iter()
+            .map(|half_extent| 2. * half_extent)
+            .product()
+    }
+
+    /// The edge lengths of the periodic cell.
+    fn period(&self) -> Option<Vector3<f32>> {
+        Some(Vector3::from_iterator(
+            self.half_extents.iter().map(|half_extent| 2. * half_extent),
+        ))
+    }
+}
@@ -0,0 +1,174 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::util::monte_carlo_volume;
+use crate::{Container, Disjoint};
+use nalgebra::{Point3, Vector3};
+use std::fmt;
+
+/// Grid resolution along each axis used to find a seed point deep inside the surface.
+const SEED_RESOLUTION: usize = 16;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+/// How the volume enclosed by an implicit surface is estimated.
+pub enum Integrator {
+    /// Evaluates the signed distance at the center of each cell of a regular grid with
+    /// `resolution` cells along each axis of the bounding box.
+    Grid {
+        /// Number of cells along each axis, at least one.
+        resolution: usize,
+    },
+    /// Evaluates the signed distance at `samples` uniformly distributed points within the
+    /// bounding box. See [monte_carlo_volume](../util/fn.monte_carlo_volume.html).
+    MonteCarlo {
+        /// Number of points sampled, at least one.
+        samples: usize,
+    },
+}
+
+impl Integrator {
+    /// Estimates the volume of the region within the box spanning `min` to `max` for which
+    /// `inside` holds. A grid without cells, or with more than a `usize` can count, or no
+    /// samples at all, is an error.
+    pub(crate) fn volume<G: Fn(&Point3<f32>) -> bool>(
+        &self,
+        min: &Point3<f32>,
+        max: &Point3<f32>,
+        inside: G,
+    ) -> Result<f32, Error> {
+        match *self {
+            Integrator::Grid { resolution: 0 } | Integrator::MonteCarlo { samples: 0 } => {
+                Err(Error::NoSamples)
+            }
+            Integrator::Grid { resolution } if resolution.checked_pow(3).is_none() => {
+                Err(Error::TooManyCells)
+            }
+            Integrator::Grid { resolution } => Ok(grid_cells(min, max, resolution)
+                .filter(|cell| inside(cell))
+                .count() as f32
+                * cell_volume(min, max, resolution)),
+            Integrator::MonteCarlo { samples } => Ok(monte_carlo_volume(min, max, samples, inside)),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// A container described implicitly by a signed distance function: negative inside the
+/// surface, positive outside and zero on it. A sphere is contained when the signed distance at
+/// its center is at most `-radius`.
+///
+/// The function need not be an exact distance, but its magnitude must never overestimate the
+/// true distance to the surface, otherwise spheres may be placed protruding from it.
+/// Both closures and boxed trait objects can be used.
+pub struct SdfContainer<F> {
+    sdf: F,
+    min: Point3<f32>,
+    max: Point3<f32>,
+    volume: f32,
+    seed: Point3<f32>,
+}
+
+impl<F: Fn(Point3<f32>) -> f32> SdfContainer<F> {
+    /// Wraps the signed distance function `sdf`, whose interior must lie within the box
+    /// spanning `min` to `max`. The enclosed volume is found once here with `integrator`.
+    pub fn new(
+        sdf: F,
+        min: Point3<f32>,
+        max: Point3<f32>,
+        integrator: Integrator,
+    ) -> Result<SdfContainer<F>, Error> {
+        if (max - min).iter().any(|extent| *extent <= 0.) {
+            return Err(Error::NegativeExtents);
+        }
+        let volume = integrator.volume(&min, &max, |point| sdf(*point) <= 0.)?;
+        // Packing starts where the surface is furthest away
+        let seed = grid_cells(&min, &max, SEED_RESOLUTION)
+            .map(|cell| (sdf(cell), cell))
+            .fold(
+                (f32::INFINITY, nalgebra::center(&min, &max)),
+                |deepest, sample| {
+                    if sample.0 < deepest.0 {
+                        sample
+                    } else {
+                        deepest
+                    }
+                },
+            )
+            .1;
+        Ok(SdfContainer {
+            sdf,
+            min,
+            max,
+            volume,
+            seed,
+        })
+    }
+
+    /// Evaluates the signed distance function at `point`.
+    pub fn distance(&self, point: Point3<f32>) -> f32 {
+        (self.sdf)(point)
+    }
+}
+
+/// Centers of the `resolution`³ cells evenly dividing the box spanning `min` to `max`.
+/// `resolution`³ must not overflow a `usize`.
+fn grid_cells<'a>(
+    min: &'a Point3<f32>,
+    max: &'a Point3<f32>,
+    resolution: usize,
+) -> impl Iterator<Item = Point3<f32>> + 'a {
+    let step = (max - min) / resolution as f32;
+    (0..resolution.pow(3)).map(move |idx| {
+        let index = Vector3::new(
+            (idx % resolution) as f32,
+            (idx / resolution % resolution) as f32,
+            (idx / resolution.pow(2)) as f32,
+        );
+        min + (index.add_scalar(0.5)).component_mul(&step)
+    })
+}
+
+/// Volume of a single cell when the box spanning `min` to `max` is divided into `resolution`³.
+fn cell_volume(min: &Point3<f32>, max: &Point3<f32>, resolution: usize) -> f32 {
+    let step = (max - min) / resolution as f32;
+    step.x * step.y * step.z
+}
+
+impl<F: Fn(Point3<f32>) -> f32> Container for SdfContainer<F> {
+    /// Checks if the signed distance at the sphere's center is at most `-radius`.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.distance(sphere.center) <= -sphere.radius
+    }
+
+    /// The volume enclosed by the surface, estimated on construction.
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Packing starts from the deepest point found on a coarse grid over the bounding box.
+    fn seed_point(&self) -> Point3<f32> {
+        self.seed
+    }
+
+    /// The box given on construction.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        Some((self.min, self.max))
+    }
+}
+
+impl<F: Fn(Point3<f32>) -> f32> Disjoint for SdfContainer<F> {
+    /// Checks if the signed distance at the sphere's center is at least `radius`.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.distance(sphere.center) >= sphere.radius
+    }
+}
+
+impl<F> fmt::Debug for SdfContainer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SdfContainer")
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("volume", &self.volume)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::errors::SphericalCowError as Error;
-use crate::Container;
-use nalgebra::{distance, Point3};
+use crate::{Container, Disjoint};
+use nalgebra::{distance, Point3, Vector3};
 use std::f32::consts::FRAC_PI_3;
 use std::fmt;
 
@@ -38,12 +38,32 @@ impl Sphere {
 impl Container for Sphere {
     /// Checks if sphere exists inside the current bounding sphere.
     fn contains(&self, sphere: &Sphere) -> bool {
-        distance(&Point3::origin(), &sphere.center) + sphere.radius <= self.radius
+        distance(&self.center, &sphere.center) + sphere.radius <= self.radius
     }
     /// Calculates the volume of this sphere in normalised units.
     fn volume(&self) -> f32 {
         self.volume()
     }
+    /// Packing starts from the center of this sphere.
+    fn seed_point(&self) -> Point3<f32> {
+        self.center
+    }
+    /// The cube circumscribing this sphere.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let extent = Vector3::repeat(self.radius);
+        Some((self.center - extent, self.center + extent))
+    }
+    /// This sphere itself.
+    fn as_sphere(&self) -> Option<Sphere> {
+        Some(self.clone())
+    }
+}
+
+impl Disjoint for Sphere {
+    /// Checks if sphere lies completely outside the current sphere.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        distance(&self.center, &sphere.center) >= self.radius + sphere.radius
+    }
 }
 
 impl fmt::Display for Sphere {
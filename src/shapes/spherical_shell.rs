@@ -0,0 +1,73 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{distance, Point3, Vector3};
+use std::f32::consts::FRAC_PI_3;
+
+#[derive(PartialEq, Debug, Clone)]
+/// Constructs the hollow region between two concentric spheres located at `center`.
+pub struct SphericalShell {
+    /// Central point in space shared by both surfaces of the shell.
+    pub center: Point3<f32>,
+    /// Radius of the hollow core.
+    pub inner_radius: f32,
+    /// Radius of the outer surface.
+    pub outer_radius: f32,
+}
+
+impl SphericalShell {
+    /// Creates a new shell at `center`. The `inner_radius` may be 0 but not negative,
+    /// and must be smaller than the `outer_radius`.
+    pub fn new(
+        center: Point3<f32>,
+        inner_radius: f32,
+        outer_radius: f32,
+    ) -> Result<SphericalShell, Error> {
+        if inner_radius < 0.0 {
+            Err(Error::NegativeRadius)
+        } else if inner_radius >= outer_radius {
+            Err(Error::InvertedShell)
+        } else {
+            Ok(SphericalShell {
+                center,
+                inner_radius,
+                outer_radius,
+            })
+        }
+    }
+}
+
+impl Container for SphericalShell {
+    /// Checks if sphere lies outside the inner surface and inside the outer one.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        let from_center = distance(&self.center, &sphere.center);
+        from_center - sphere.radius >= self.inner_radius
+            && from_center + sphere.radius <= self.outer_radius
+    }
+
+    /// Calculates the volume of this shell in normalised units.
+    fn volume(&self) -> f32 {
+        4. * FRAC_PI_3 * (self.outer_radius.powi(3) - self.inner_radius.powi(3))
+    }
+
+    /// Packing starts midway through the wall of the shell, along x.
+    fn seed_point(&self) -> Point3<f32> {
+        self.center + Vector3::x() * 0.5 * (self.inner_radius + self.outer_radius)
+    }
+
+    /// The cube circumscribing the outer surface.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let extent = Vector3::repeat(self.outer_radius);
+        Some((self.center - extent, self.center + extent))
+    }
+}
+
+impl Disjoint for SphericalShell {
+    /// Checks if sphere lies completely outside the wall of the current shell, including
+    /// within its hollow core.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        let from_center = distance(&self.center, &sphere.center);
+        from_center + sphere.radius <= self.inner_radius
+            || from_center - sphere.radius >= self.outer_radius
+    }
+}
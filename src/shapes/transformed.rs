@@ -0,0 +1,101 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::{Container, Disjoint};
+use nalgebra::{Isometry3, Point3};
+
+#[derive(PartialEq, Debug, Clone)]
+/// Places any `inner` container into a scene. The inner container is first scaled uniformly
+/// about its own origin, then rotated and translated by `isometry`.
+///
+/// Periodic containers are assumed to be centered at the origin, so the `period` of the inner
+/// container is not carried over: a transformed container is always closed.
+pub struct Transformed<C> {
+    /// The container, described in its own local frame.
+    pub inner: C,
+    /// Rigid transform taking the scaled inner container into world coordinates.
+    pub isometry: Isometry3<f32>,
+    /// Uniform scale applied to the inner container, which must be greater than 0.
+    pub scale: f32,
+}
+
+impl<C: Container> Transformed<C> {
+    /// Wraps `inner`, scaling it by `scale` then applying `isometry`.
+    pub fn new(inner: C, isometry: Isometry3<f32>, scale: f32) -> Result<Transformed<C>, Error> {
+        if scale <= 0.0 {
+            Err(Error::NegativeScale)
+        } else {
+            Ok(Transformed {
+                inner,
+                isometry,
+                scale,
+            })
+        }
+    }
+
+    /// Maps a point in world coordinates into the local frame of the inner container.
+    fn to_local(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.isometry.inverse_transform_point(point) / self.scale
+    }
+
+    /// Maps a sphere in world coordinates into the local frame of the inner container.
+    fn to_local_sphere(&self, sphere: &Sphere) -> Sphere {
+        Sphere {
+            center: self.to_local(&sphere.center),
+            radius: sphere.radius / self.scale,
+        }
+    }
+
+    /// Maps a point in the local frame of the inner container into world coordinates.
+    fn to_world(&self, point: &Point3<f32>) -> Point3<f32> {
+        self.isometry.transform_point(&(point * self.scale))
+    }
+}
+
+impl<C: Container> Container for Transformed<C> {
+    /// Checks if the sphere, once taken into the inner container's frame, is contained there.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.inner.contains(&self.to_local_sphere(sphere))
+    }
+
+    /// The volume of the inner container, scaled accordingly.
+    fn volume(&self) -> f32 {
+        self.inner.volume() * self.scale.powi(3)
+    }
+
+    /// The inner container's seed point, moved into world coordinates.
+    fn seed_point(&self) -> Point3<f32> {
+        self.to_world(&self.inner.seed_point())
+    }
+
+    /// The axis aligned box enclosing all eight transformed corners of the inner
+    /// container's bounding box.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let (min, max) = self.inner.bounding_box()?;
+        let corners = (0..8).map(|corner| {
+            self.to_world(&Point3::new(
+                if corner & 1 == 0 { min.x } else { max.x },
+                if corner & 2 == 0 { min.y } else { max.y },
+                if corner & 4 == 0 { min.z } else { max.z },
+            ))
+        });
+        let first = self.to_world(&min);
+        Some(corners.fold((first, first), |(lo, hi), corner| {
+            (lo.inf(&corner), hi.sup(&corner))
+        }))
+    }
+
+    /// A transformed sphere is still a sphere, moved into world coordinates.
+    fn as_sphere(&self) -> Option<Sphere> {
+        self.inner.as_sphere().map(|sphere| Sphere {
+            center: self.to_world(&sphere.center),
+            radius: sphere.radius * self.scale,
+        })
+    }
+}
+
+impl<C: Container + Disjoint> Disjoint for Transformed<C> {
+    /// Checks if the sphere, once taken into the inner container's frame, is disjoint from it.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.inner.is_disjoint(&self.to_local_sphere(sphere))
+    }
+}
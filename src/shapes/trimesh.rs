@@ -0,0 +1,157 @@
+use crate::errors::SphericalCowError as Error;
+use crate::shapes::Sphere;
+use crate::util::bvh::Bvh;
+use crate::util::{check_faces, mass_properties, normalizing_scale, MassProperties, MeshSize};
+use crate::{Container, Disjoint};
+use nalgebra::{Matrix3, Point3};
+
+#[derive(PartialEq, Debug, Clone)]
+/// A closed triangle mesh, such as one loaded from an obj file, built from a list of `vertices`
+/// and the `faces` indexing them. Faces are wound anticlockwise when viewed from outside the mesh;
+/// a mesh wound the other way round is flipped on construction.
+pub struct TriMesh {
+    vertices: Vec<Point3<f32>>,
+    faces: Vec<[usize; 3]>,
+    /// Vertex locations of each face, arranged for ray casting and distance queries.
+    bvh: Bvh,
+    properties: MassProperties,
+}
+
+impl TriMesh {
+    /// Constructs a mesh from its `vertices` and the triplet of vertex indices making up each face.
+    /// Every edge must be shared by exactly two faces which wind in opposite directions along it,
+    /// otherwise the mesh does not bound a volume we can pack. Nor can a mesh which encloses no
+    /// volume, such as one which is flat.
+    pub fn new(vertices: Vec<Point3<f32>>, mut faces: Vec<[usize; 3]>) -> Result<TriMesh, Error> {
+        check_faces(&faces, vertices.len())?;
+
+        let mut triangles: Vec<_> = faces
+            .iter()
+            .map(|&[a, b, c]| (vertices[a], vertices[b], vertices[c]))
+            .collect();
+        let mut properties = mass_properties(&triangles);
+        if properties.volume < 0. {
+            // Normals face inwards
+            for face in faces.iter_mut() {
+                face.swap(1, 2);
+            }
+            for triangle in triangles.iter_mut() {
+                std::mem::swap(&mut triangle.1, &mut triangle.2);
+            }
+            properties.volume = -properties.volume;
+        }
+        if properties.volume <= f32::EPSILON {
+            return Err(Error::DegenerateMesh);
+        }
+        Ok(TriMesh {
+            vertices,
+            faces,
+            bvh: Bvh::new(triangles),
+            properties,
+        })
+    }
+
+    /// Returns a copy of the mesh translated so that `center` sits at the origin, then scaled
+    /// uniformly about it to `size`. See [normalize_trimesh](../util/fn.normalize_trimesh.html).
+    pub fn normalized(&self, center: &Point3<f32>, size: MeshSize) -> Result<TriMesh, Error> {
+        let scale = normalizing_scale(self.triangles(), size)?;
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| Point3::from((vertex - center) * scale))
+            .collect();
+        TriMesh::new(vertices, self.faces.clone())
+    }
+
+    /// Centroid of the volume enclosed by the mesh.
+    pub fn centroid(&self) -> Point3<f32> {
+        self.properties.centroid
+    }
+
+    /// Inertia tensor of the volume enclosed by the mesh about its centroid, for a uniform
+    /// density of one.
+    pub fn inertia(&self) -> Matrix3<f32> {
+        self.properties.inertia
+    }
+
+    /// Location of each vertex of the mesh.
+    pub fn vertices(&self) -> &[Point3<f32>] {
+        &self.vertices
+    }
+
+    /// Indices of the three vertices making up each face, wound anticlockwise when viewed from
+    /// outside the mesh.
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+
+    /// Vertex locations of each face, in the form used by the functions in
+    /// [util](../util/index.html).
+    pub fn triangles(&self) -> &[(Point3<f32>, Point3<f32>, Point3<f32>)] {
+        self.bvh.triangles()
+    }
+
+    /// The bounding volume hierarchy over the faces, which answers ray casting and closest point
+    /// queries without visiting every face.
+    pub fn bvh(&self) -> &Bvh {
+        &self.bvh
+    }
+
+    /// Checks that no face of the mesh passes through the sphere.
+    fn clear_of_faces(&self, sphere: &Sphere) -> bool {
+        self.bvh.is_clear(&sphere.center, sphere.radius)
+    }
+}
+
+impl Container for TriMesh {
+    /// Checks if sphere exists inside the mesh: its center must lie inside, and every face must be at
+    /// least one radius away from it so no part of the sphere pokes through.
+    fn contains(&self, sphere: &Sphere) -> bool {
+        self.clear_of_faces(sphere) && self.bvh.contains_point(&sphere.center)
+    }
+
+    /// Calculates the volume enclosed by the mesh.
+    fn volume(&self) -> f32 {
+        self.properties.volume
+    }
+
+    /// Packing starts from the centroid of the mesh. Should it lie outside of a concave mesh, a seed
+    /// is found elsewhere within the bounding box.
+    fn seed_point(&self) -> Point3<f32> {
+        self.centroid()
+    }
+
+    /// The smallest box enclosing every vertex.
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let first = self.vertices.first()?;
+        Some(
+            self.vertices
+                .iter()
+                .fold((*first, *first), |(min, max), v| (min.inf(v), max.sup(v))),
+        )
+    }
+}
+
+impl Disjoint for TriMesh {
+    /// Checks if sphere lies completely outside the mesh.
+    fn is_disjoint(&self, sphere: &Sphere) -> bool {
+        self.clear_of_faces(sphere) && !self.bvh.contains_point(&sphere.center)
+    }
+}
+
+#[test]
+fn trimesh_flips_inward_faces() {
+    let vertices = vec![
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(0., 0., 1.),
+    ];
+    let outward = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]];
+    let inward = outward.iter().map(|&[a, b, c]| [a, c, b]).collect();
+
+    let mesh = TriMesh::new(vertices.clone(), inward).unwrap();
+    assert_eq!(mesh.faces(), &outward[..]);
+    assert!((mesh.volume() - 1. / 6.).abs() < 1e-6);
+    assert_eq!(mesh, TriMesh::new(vertices, outward).unwrap());
+}
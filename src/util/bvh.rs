@@ -0,0 +1,369 @@
+//! A bounding volume hierarchy over the faces of a triangle mesh, so that ray casts and distance queries only visit
+//! the faces near them rather than every face of the mesh.
+
+use super::{
+    closest_point_on_triangle, majority_inside, ray_triangle_distance, ray_triangle_hit,
+    winding_number, RayHit,
+};
+use nalgebra::{Point3, Vector3};
+use std::cmp::Ordering;
+
+/// Most triangles held by a single leaf of the hierarchy.
+const LEAF_SIZE: usize = 4;
+
+#[derive(PartialEq, Debug, Clone)]
+struct Node {
+    min: Point3<f32>,
+    max: Point3<f32>,
+    /// Leaves hold the triangles `order[first..first + count]`. Branches have a `count` of zero,
+    /// with their two children stored at `first` and `first + 1`.
+    first: usize,
+    count: usize,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+/// Axis aligned boxes nested about the triangles of a mesh. Built once, each query then descends only into the
+/// boxes it can touch.
+pub struct Bvh {
+    triangles: Vec<(Point3<f32>, Point3<f32>, Point3<f32>)>,
+    /// Indices into `triangles`, grouped by leaf.
+    order: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy by repeatedly splitting `triangles` at the median of their centroids along the axis
+    /// over which the centroids are most spread.
+    pub fn new(triangles: Vec<(Point3<f32>, Point3<f32>, Point3<f32>)>) -> Bvh {
+        let centroids: Vec<Point3<f32>> = triangles
+            .iter()
+            .map(|&(a, b, c)| Point3::from((a.coords + b.coords + c.coords) / 3.))
+            .collect();
+        let mut order: Vec<usize> = (0..triangles.len()).collect();
+        let mut nodes = Vec::new();
+        let mut pending = Vec::new();
+        if !triangles.is_empty() {
+            nodes.push(Node {
+                min: Point3::origin(),
+                max: Point3::origin(),
+                first: 0,
+                count: triangles.len(),
+            });
+            pending.push(0);
+        }
+        while let Some(idx) = pending.pop() {
+            let (first, count) = (nodes[idx].first, nodes[idx].count);
+            let members = &mut order[first..first + count];
+            let (min, max) = bounds(
+                members
+                    .iter()
+                    .map(|&t| triangles[t].0)
+                    .chain(members.iter().map(|&t| triangles[t].1))
+                    .chain(members.iter().map(|&t| triangles[t].2)),
+            );
+            // Pad the box so rounding never lets a ray slip past a triangle lying on its faces
+            let pad = Vector3::repeat(
+                1e-5 * (min.coords.abs().sup(&max.coords.abs()).max() + (max - min).norm()),
+            );
+            nodes[idx].min = min - pad;
+            nodes[idx].max = max + pad;
+            if count <= LEAF_SIZE {
+                continue;
+            }
+
+            let (lower, upper) = bounds(members.iter().map(|&t| centroids[t]));
+            let axis = (upper - lower).imax();
+            let half = count / 2;
+            members.select_nth_unstable_by(half, |&a, &b| {
+                centroids[a][axis]
+                    .partial_cmp(&centroids[b][axis])
+                    .unwrap_or(Ordering::Equal)
+            });
+            let child = nodes.len();
+            for &(first, count) in [(first, half), (first + half, count - half)].iter() {
+                nodes.push(Node {
+                    min,
+                    max,
+                    first,
+                    count,
+                });
+            }
+            nodes[idx].first = child;
+            nodes[idx].count = 0;
+            pending.push(child);
+            pending.push(child + 1);
+        }
+
+        Bvh {
+            triangles,
+            order,
+            nodes,
+        }
+    }
+
+    /// The triangles held by the hierarchy, in the order given on construction.
+    pub fn triangles(&self) -> &[(Point3<f32>, Point3<f32>, Point3<f32>)] {
+        &self.triangles
+    }
+
+    /// Equivalent to [ray_intersection_count](../fn.ray_intersection_count.html): counts the faces crossed by a ray
+    /// leaving the origin in the direction `dir` before it reaches a distance of `o_dist`.
+    pub fn ray_intersection_count(&self, dir: Vector3<f32>, o_dist: f32) -> i32 {
+        let origin = Point3::origin();
+        let inv_dir = dir.map(|d| 1. / d);
+        let mut count = 0;
+        self.search(
+            |min, max| ray_hits_box(&origin, &inv_dir, min, max, o_dist),
+            |triangle| {
+                if let Some(t) = ray_triangle_distance(&origin, &dir, triangle) {
+                    if t.is_sign_positive() && t < o_dist {
+                        count += 1;
+                    }
+                }
+                true
+            },
+        );
+        count
+    }
+
+    /// Equivalent to [ray_crossing_count](../fn.ray_crossing_count.html): counts the faces crossed by a ray leaving
+    /// `origin` in the direction `dir`, for any distance along it.
+    pub fn ray_crossing_count(&self, origin: &Point3<f32>, dir: &Vector3<f32>) -> usize {
+        let inv_dir = dir.map(|d| 1. / d);
+        let mut count = 0;
+        self.search(
+            |min, max| ray_hits_box(origin, &inv_dir, min, max, f32::INFINITY),
+            |triangle| {
+                if ray_triangle_distance(origin, dir, triangle).map_or(false, |t| t > 0.) {
+                    count += 1;
+                }
+                true
+            },
+        );
+        count
+    }
+
+    /// Equivalent to [robust_ray_crossing_count](../fn.robust_ray_crossing_count.html): counts the faces crossed by a
+    /// ray leaving `origin` in the direction `dir`, or `None` if it grazes an edge, vertex or face.
+    pub fn robust_ray_crossing_count(
+        &self,
+        origin: &Point3<f32>,
+        dir: &Vector3<f32>,
+    ) -> Option<usize> {
+        let inv_dir = dir.map(|d| 1. / d);
+        let mut count = 0;
+        let clean = self.search(
+            |min, max| ray_hits_box(origin, &inv_dir, min, max, f32::INFINITY),
+            |triangle| match ray_triangle_hit(origin, dir, triangle) {
+                RayHit::Miss => true,
+                RayHit::Cross => {
+                    count += 1;
+                    true
+                }
+                RayHit::Graze => false,
+            },
+        );
+        if clean {
+            Some(count)
+        } else {
+            None
+        }
+    }
+
+    /// Equivalent to [point_in_trimesh](../fn.point_in_trimesh.html): checks if `point` lies inside the closed mesh.
+    pub fn contains_point(&self, point: &Point3<f32>) -> bool {
+        majority_inside(
+            |dir| self.robust_ray_crossing_count(point, dir),
+            || winding_number(&self.triangles, point) >= 0.5,
+        )
+    }
+
+    /// Checks that no triangle comes closer than `radius` to `point`.
+    pub fn is_clear(&self, point: &Point3<f32>, radius: f32) -> bool {
+        self.search(
+            |min, max| box_distance(point, min, max) < radius,
+            |triangle| {
+                nalgebra::distance(point, &closest_point_on_triangle(point, triangle)) >= radius
+            },
+        )
+    }
+
+    /// The point on any triangle closest to `point`, or `None` if the hierarchy holds no triangles.
+    pub fn closest_point(&self, point: &Point3<f32>) -> Option<Point3<f32>> {
+        let mut closest = None;
+        let mut best = f32::INFINITY;
+        let mut pending = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(idx) = pending.pop() {
+            let node = &self.nodes[idx];
+            if box_distance(point, &node.min, &node.max) >= best {
+                continue;
+            }
+            if node.count == 0 {
+                // Descend into the nearer child first, so more of the further one is pruned
+                let gap =
+                    |idx: usize| box_distance(point, &self.nodes[idx].min, &self.nodes[idx].max);
+                let (near, far) = if gap(node.first) <= gap(node.first + 1) {
+                    (node.first, node.first + 1)
+                } else {
+                    (node.first + 1, node.first)
+                };
+                pending.push(far);
+                pending.push(near);
+                continue;
+            }
+            for &t in self.order[node.first..node.first + node.count].iter() {
+                let candidate = closest_point_on_triangle(point, &self.triangles[t]);
+                let dist = nalgebra::distance(point, &candidate);
+                if dist < best {
+                    best = dist;
+                    closest = Some(candidate);
+                }
+            }
+        }
+        closest
+    }
+
+    /// Visits every triangle within the boxes accepted by `enter`, stopping early if `visit` returns `false`.
+    /// Returns `false` if the search was stopped early.
+    fn search<E, V>(&self, enter: E, mut visit: V) -> bool
+    where
+        E: Fn(&Point3<f32>, &Point3<f32>) -> bool,
+        V: FnMut(&(Point3<f32>, Point3<f32>, Point3<f32>)) -> bool,
+    {
+        let mut pending = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(idx) = pending.pop() {
+            let node = &self.nodes[idx];
+            if !enter(&node.min, &node.max) {
+                continue;
+            }
+            if node.count == 0 {
+                pending.push(node.first);
+                pending.push(node.first + 1);
+            } else if !self.order[node.first..node.first + node.count]
+                .iter()
+                .all(|&t| visit(&self.triangles[t]))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The smallest box enclosing all `points`.
+fn bounds<I: Iterator<Item = Point3<f32>>>(points: I) -> (Point3<f32>, Point3<f32>) {
+    points.fold(
+        (
+            Point3::from(Vector3::repeat(f32::INFINITY)),
+            Point3::from(Vector3::repeat(f32::NEG_INFINITY)),
+        ),
+        |(min, max), point| (min.inf(&point), max.sup(&point)),
+    )
+}
+
+/// Checks if a ray leaving `origin`, with the reciprocal of its direction `inv_dir`, passes through the box spanning
+/// `min` to `max` before reaching a distance of `limit`. Comparisons are arranged so an undefined slab, from a ray
+/// lying in one of its planes, counts as a hit.
+fn ray_hits_box(
+    origin: &Point3<f32>,
+    inv_dir: &Vector3<f32>,
+    min: &Point3<f32>,
+    max: &Point3<f32>,
+    limit: f32,
+) -> bool {
+    let to_min = (min - origin).component_mul(inv_dir);
+    let to_max = (max - origin).component_mul(inv_dir);
+    let near = to_min.zip_map(&to_max, f32::min).max();
+    let far = to_min.zip_map(&to_max, f32::max).min();
+    !(near > far || far < 0. || near > limit)
+}
+
+/// Distance from `point` to the box spanning `min` to `max`, zero if it lies within.
+fn box_distance(point: &Point3<f32>, min: &Point3<f32>, max: &Point3<f32>) -> f32 {
+    (min - point)
+        .sup(&(point - max))
+        .sup(&Vector3::zeros())
+        .norm()
+}
+
+#[cfg(test)]
+/// Randomly placed and sized triangles, which need not form a closed mesh.
+fn soup() -> Vec<(Point3<f32>, Point3<f32>, Point3<f32>)> {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let point = |rng: &mut StdRng| {
+        Point3::new(
+            rng.gen_range(-5.0..5.0),
+            rng.gen_range(-5.0..5.0),
+            rng.gen_range(-5.0..5.0),
+        )
+    };
+    (0..300)
+        .map(|_| {
+            let a = point(&mut rng);
+            let offset = |rng: &mut StdRng| Vector3::new(rng.gen(), rng.gen(), rng.gen());
+            (a, a + offset(&mut rng), a + offset(&mut rng))
+        })
+        .collect()
+}
+
+#[test]
+fn bvh_matches_brute_force() {
+    use crate::util::{
+        point_triangle_distance, ray_crossing_count, ray_intersection_count,
+        robust_ray_crossing_count,
+    };
+    use rand::{Rng, SeedableRng};
+
+    let triangles = soup();
+    let bvh = Bvh::new(triangles.clone());
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    for _ in 0..200 {
+        let dir = Vector3::new(
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+        )
+        .normalize();
+        let origin = Point3::from(dir * rng.gen_range(-3.0..3.0));
+        assert_eq!(
+            bvh.ray_intersection_count(dir, 4.),
+            ray_intersection_count(&triangles, dir, 4.)
+        );
+        assert_eq!(
+            bvh.ray_crossing_count(&origin, &dir),
+            ray_crossing_count(&triangles, &origin, &dir)
+        );
+        assert_eq!(
+            bvh.robust_ray_crossing_count(&origin, &dir),
+            robust_ray_crossing_count(&triangles, &origin, &dir)
+        );
+
+        let nearest = triangles
+            .iter()
+            .map(|triangle| point_triangle_distance(&origin, triangle))
+            .fold(f32::INFINITY, f32::min);
+        let closest = bvh.closest_point(&origin).unwrap();
+        assert!((nalgebra::distance(&origin, &closest) - nearest).abs() < 1e-5);
+        assert!(bvh.is_clear(&origin, nearest * 0.99));
+        assert!(!bvh.is_clear(&origin, nearest * 1.01));
+    }
+}
+
+#[test]
+fn empty_bvh() {
+    let bvh = Bvh::new(Vec::new());
+    assert_eq!(bvh.ray_crossing_count(&Point3::origin(), &Vector3::x()), 0);
+    assert_eq!(bvh.closest_point(&Point3::origin()), None);
+    assert!(bvh.is_clear(&Point3::origin(), 1.));
+}
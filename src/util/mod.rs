@@ -1,11 +1,24 @@
-//! Useful helper functions such as a fast ray casting method and volume finder for use with arbitrary shaped triangular meshes.
+//! Useful helper functions such as a fast ray casting method, inside test, point to triangle distance and volume finder for use with arbitrary shaped triangular meshes,
+//! along with a Monte Carlo volume estimate for shapes without an analytic volume.
 
-use nalgebra::{Matrix, Point3, Vector3};
+use crate::errors::SphericalCowError as Error;
+use nalgebra::{Matrix, Matrix3, Point3, Vector3};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+pub mod bvh;
+
+/// The three vertices of a triangle, as taken by the functions handling trimeshes.
+pub type Triangle = (Point3<f32>, Point3<f32>, Point3<f32>);
 
 /// Find the baycentric coordinates `(u,v)` and distance `t` given three triangle veriticies `vert0`, `vert1`, `vert2` and the
 /// unit vector `dir` (`D`) in the direction of a ray `R(t) = O + tD` such that `R(t)` is equivalent to a point `T(u,v)` on
 /// triangle `T`. Here, `T(u,v) = (1-u-v)V_0+uV_1+uV_2`. If distance `t` is less than the distance of our sphere from the
 /// origin (`sphere_dist`), then add one to the count.
+///
+/// A ray passing through an edge or vertex shared between faces is counted by each of them, so the parity of this
+/// count is unreliable in such cases. Use `robust_ray_crossing_count` or `point_in_trimesh` to classify points.
 pub fn ray_intersection_count(
     triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
     dir: Vector3<f32>,
@@ -13,39 +26,264 @@ pub fn ray_intersection_count(
 ) -> i32 {
     triangles
         .iter()
-        .map(|triangle| {
-            let &(vert0, vert1, vert2) = triangle;
-            let edge1 = vert1 - vert0;
-            let edge2 = vert2 - vert0;
-            let pvec = Matrix::cross(&dir, &edge2);
-            let det = Matrix::dot(&edge1, &pvec);
-            if det > -1e-6 && det < 1e-6 {
-                return 0;
-            }
-            let inv_det = 1. / det;
-            let tvec = Point3::origin() - vert0;
-            let u = Matrix::dot(&tvec, &pvec) * inv_det;
-            if !(0. ..=1.).contains(&u) {
-                return 0;
-            }
-            let qvec = Matrix::cross(&tvec, &edge1);
-            let v = Matrix::dot(&dir, &qvec) * inv_det;
-            if v < 0. || u + v > 1. {
-                return 0;
-            }
-            // Our ray R(t) = O + tD can now be calculated
-            let t = Matrix::dot(&edge2, &qvec) * inv_det;
-            if t.is_sign_positive() && t < o_dist {
-                1
-            } else {
-                0
+        .filter_map(|triangle| ray_triangle_distance(&Point3::origin(), &dir, triangle))
+        .filter(|t| t.is_sign_positive() && *t < o_dist)
+        .count() as i32
+}
+
+/// Counts the faces crossed by a ray leaving `origin` in the direction `dir`, for any distance along it. Shares the
+/// caveats of `ray_intersection_count` for rays through edges and vertices.
+pub fn ray_crossing_count(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+    origin: &Point3<f32>,
+    dir: &Vector3<f32>,
+) -> usize {
+    triangles
+        .iter()
+        .filter_map(|triangle| ray_triangle_distance(origin, dir, triangle))
+        .filter(|t| *t > 0.)
+        .count()
+}
+
+/// Counts the faces crossed by a ray leaving `origin` in the direction `dir`, or `None` if the ray passes so close
+/// to an edge or vertex, or skims so close along a face, that whether it crosses there cannot be trusted. The ray
+/// also fails if `origin` lies on a face. A `Some` count therefore has a reliable parity.
+pub fn robust_ray_crossing_count(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+    origin: &Point3<f32>,
+    dir: &Vector3<f32>,
+) -> Option<usize> {
+    triangles.iter().try_fold(0, |count, triangle| {
+        match ray_triangle_hit(origin, dir, triangle) {
+            RayHit::Miss => Some(count),
+            RayHit::Cross => Some(count + 1),
+            RayHit::Graze => None,
+        }
+    })
+}
+
+/// Relative tolerance within which a ray is considered to graze an edge, vertex or face of a triangle.
+const GRAZE_TOLERANCE: f32 = 1e-5;
+
+/// How a ray meets a triangle.
+pub(crate) enum RayHit {
+    /// The ray clearly passes by.
+    Miss,
+    /// The ray clearly passes through the interior of the triangle.
+    Cross,
+    /// The ray comes too close to an edge or vertex, runs along the triangle's plane, or starts on the triangle.
+    Graze,
+}
+
+/// Classifies how the ray `R(t) = O + tD` from `origin` in the direction `dir` meets `triangle`, by the same method as
+/// `ray_triangle_distance` but with tolerances relative to the size of the triangle, rather than fixed.
+pub(crate) fn ray_triangle_hit(
+    origin: &Point3<f32>,
+    dir: &Vector3<f32>,
+    triangle: &(Point3<f32>, Point3<f32>, Point3<f32>),
+) -> RayHit {
+    let &(vert0, vert1, vert2) = triangle;
+    let edge1 = vert1 - vert0;
+    let edge2 = vert2 - vert0;
+    let normal = Matrix::cross(&edge1, &edge2);
+    let length = edge1.norm().max(edge2.norm());
+    let tvec = origin - vert0;
+    let pvec = Matrix::cross(dir, &edge2);
+    let det = Matrix::dot(&edge1, &pvec);
+    if det.abs() <= GRAZE_TOLERANCE * normal.norm() * dir.norm() {
+        // The ray runs along the plane of the triangle, so may skim across it
+        return if Matrix::dot(&tvec, &normal).abs() <= GRAZE_TOLERANCE * length * normal.norm() {
+            RayHit::Graze
+        } else {
+            RayHit::Miss
+        };
+    }
+    let inv_det = 1. / det;
+    let u = Matrix::dot(&tvec, &pvec) * inv_det;
+    let qvec = Matrix::cross(&tvec, &edge1);
+    let v = Matrix::dot(dir, &qvec) * inv_det;
+    let w = 1. - u - v;
+    let t = Matrix::dot(&edge2, &qvec) * inv_det;
+    let near_t = GRAZE_TOLERANCE * length / dir.norm();
+    if u < -GRAZE_TOLERANCE || v < -GRAZE_TOLERANCE || w < -GRAZE_TOLERANCE || t < -near_t {
+        RayHit::Miss
+    } else if u <= GRAZE_TOLERANCE || v <= GRAZE_TOLERANCE || w <= GRAZE_TOLERANCE || t <= near_t {
+        RayHit::Graze
+    } else {
+        RayHit::Cross
+    }
+}
+
+/// Distance `t` along the ray `R(t) = O + tD` from `origin` in the direction `dir` at which it crosses `triangle`,
+/// following the method of Möller and Trumbore. Rays lying in the plane of the triangle never cross it.
+pub(crate) fn ray_triangle_distance(
+    origin: &Point3<f32>,
+    dir: &Vector3<f32>,
+    triangle: &(Point3<f32>, Point3<f32>, Point3<f32>),
+) -> Option<f32> {
+    let &(vert0, vert1, vert2) = triangle;
+    let edge1 = vert1 - vert0;
+    let edge2 = vert2 - vert0;
+    let pvec = Matrix::cross(dir, &edge2);
+    let det = Matrix::dot(&edge1, &pvec);
+    if det > -1e-6 && det < 1e-6 {
+        return None;
+    }
+    let inv_det = 1. / det;
+    let tvec = origin - vert0;
+    let u = Matrix::dot(&tvec, &pvec) * inv_det;
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+    let qvec = Matrix::cross(&tvec, &edge1);
+    let v = Matrix::dot(dir, &qvec) * inv_det;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+    // Our ray R(t) = O + tD can now be calculated
+    Some(Matrix::dot(&edge2, &qvec) * inv_det)
+}
+
+/// Directions of the first rays cast by `point_in_trimesh`. None are aligned with the axes or with each other, so
+/// meshes built on a grid rarely present an edge or vertex to more than one of them.
+const PROBE_DIRECTIONS: [[f32; 3]; 3] = [
+    [0.802_829, 0.255_533, 0.538_747],
+    [-0.116_258, 0.822_219, 0.557_183],
+    [0.184_654, -0.510_342, 0.839_901],
+];
+
+/// Most rays cast by `point_in_trimesh` before it falls back to the `winding_number`.
+const MAX_PROBES: usize = 16;
+
+/// Checks if `point` lies inside the closed trimesh made up of `triangles`. A ray crossing an odd number of faces
+/// starts inside the mesh. Rays grazing an edge, vertex or face are discarded and another cast in a random direction
+/// in their place, until three give a clean count and the majority verdict is taken. Should that fail, as it will for
+/// points on the surface, the `winding_number` decides.
+pub fn point_in_trimesh(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+    point: &Point3<f32>,
+) -> bool {
+    majority_inside(
+        |dir| robust_ray_crossing_count(triangles, point, dir),
+        || winding_number(triangles, point) >= 0.5,
+    )
+}
+
+/// Casts the rays used by `point_in_trimesh`, with `crossings` counting the faces each one crosses, or returning
+/// `None` if the ray grazes the mesh. Random directions are drawn from a fixed seed, so a point is always classified
+/// the same way.
+pub(crate) fn majority_inside<F, G>(crossings: F, fallback: G) -> bool
+where
+    F: Fn(&Vector3<f32>) -> Option<usize>,
+    G: FnOnce() -> bool,
+{
+    let mut rng = StdRng::seed_from_u64(0);
+    let (mut inside, mut outside) = (0, 0);
+    for probe in 0..MAX_PROBES {
+        let dir = match PROBE_DIRECTIONS.get(probe) {
+            Some(dir) => Vector3::new(dir[0], dir[1], dir[2]),
+            None => Vector3::from_fn(|_, _| rng.gen_range(-1.0..1.0)),
+        };
+        match crossings(&dir) {
+            Some(count) if count & 1 == 1 => inside += 1,
+            Some(_) => outside += 1,
+            None => continue,
+        }
+        if inside == 2 || outside == 2 {
+            return inside == 2;
+        }
+    }
+    fallback()
+}
+
+/// Points within this distance of the plane of a face, relative to the magnitude of their coordinates, are taken
+/// to lie in it by `winding_number`. A few times the precision of `f32`, so points rounded onto a face still count.
+const PLANE_TOLERANCE: f64 = 4. * f32::EPSILON as f64;
+
+/// The generalized winding number of a closed trimesh about `point` (see Jacobson et al., ACM Trans. Graph. 32, 33
+/// (2013)): the solid angle subtended by each face, as given by Van Oosterom and Strackee, summed and divided by 4π.
+/// It is one inside the trimesh and zero outside. Faces the point lies in the plane of subtend nothing, so on the
+/// surface it takes the fraction of a small sphere about the point lying inside, e.g. 0.5 on a face.
+pub fn winding_number(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+    point: &Point3<f32>,
+) -> f32 {
+    let point = point.coords.map(f64::from);
+    let solid_angle: f64 = triangles
+        .iter()
+        .map(|(a, b, c)| {
+            let a = a.coords.map(f64::from) - point;
+            let b = b.coords.map(f64::from) - point;
+            let c = c.coords.map(f64::from) - point;
+            let (la, lb, lc) = (a.norm(), b.norm(), c.norm());
+            let det = a.dot(&b.cross(&c));
+            // `det` is the distance from the point to the plane of the face, times twice its area
+            let scale = point.norm() + la.max(lb).max(lc);
+            if det.abs() <= PLANE_TOLERANCE * scale * (b - a).cross(&(c - a)).norm() {
+                return 0.;
             }
+            2. * det.atan2(la * lb * lc + a.dot(&b) * lc + b.dot(&c) * la + c.dot(&a) * lb)
         })
-        .sum()
+        .sum();
+    (solid_angle / (4. * std::f64::consts::PI)) as f32
+}
+
+/// The point on `triangle` closest to `point`, found by identifying which vertex, edge or face region of the
+/// triangle the point projects onto (see Ericson, Real-Time Collision Detection, §5.1.5).
+pub fn closest_point_on_triangle(
+    point: &Point3<f32>,
+    triangle: &(Point3<f32>, Point3<f32>, Point3<f32>),
+) -> Point3<f32> {
+    let &(a, b, c) = triangle;
+    let ab = b - a;
+    let ac = c - a;
+    let ap = point - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0. && d2 <= 0. {
+        return a;
+    }
+    let bp = point - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0. && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = point - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0. && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    // Inside the face
+    let denom = 1. / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
 }
 
-/// Identify the volume of a trimesh which contains the cartesian origin (0, 0, 0).
-/// Consider the tetrahedron created by any triangle and the origin OABC and sum thier volumes.
+/// Shortest distance from `point` to any point on `triangle`.
+pub fn point_triangle_distance(
+    point: &Point3<f32>,
+    triangle: &(Point3<f32>, Point3<f32>, Point3<f32>),
+) -> f32 {
+    nalgebra::distance(point, &closest_point_on_triangle(point, triangle))
+}
+
+/// Identify the signed volume of a closed trimesh.
+/// Consider the tetrahedron created by any triangle and the origin OABC and sum thier volumes. Tetrahedra outside the
+/// trimesh cancel, so the origin need not lie inside it, but the result is negative if the faces are wound inwards and
+/// meaningless if the trimesh is not closed. See `trimesh_properties` for a checked alternative.
 pub fn trimesh_volume(triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)]) -> f32 {
     let sixth = 1. / 6.;
     triangles
@@ -60,3 +298,269 @@ pub fn trimesh_volume(triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)]) ->
         })
         .sum()
 }
+
+/// Identify the centroid of the volume enclosed by a closed trimesh. See `trimesh_properties` for a checked
+/// alternative.
+pub fn trimesh_centroid(triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)]) -> Point3<f32> {
+    mass_properties(triangles).centroid
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+/// Volume, centroid and inertia of the solid enclosed by a closed trimesh.
+pub struct MassProperties {
+    /// Volume enclosed by the trimesh, positive whichever way its faces are wound.
+    pub volume: f32,
+    /// Centroid of the enclosed volume.
+    pub centroid: Point3<f32>,
+    /// Inertia tensor of the enclosed volume about its centroid, for a uniform density of one.
+    pub inertia: Matrix3<f32>,
+}
+
+/// Finds the volume, centroid and inertia of a closed trimesh, which may lie anywhere in space. Triangles sharing a
+/// corner must agree on its location exactly. Fails if the trimesh is not closed, or its faces are not consistently
+/// wound.
+pub fn trimesh_properties(triangles: &[Triangle]) -> Result<MassProperties, Error> {
+    let (vertices, faces) = index_triangles(triangles);
+    check_faces(&faces, vertices.len())?;
+    let mut properties = mass_properties(triangles);
+    properties.volume = properties.volume.abs();
+    Ok(properties)
+}
+
+/// Mass properties of a trimesh assumed to be closed, with a volume which is negative if its faces are wound inwards.
+/// Sums signed tetrahedra joining each triangle to one of the trimesh's own vertices rather than the origin, in double
+/// precision, so a trimesh far from the origin loses no accuracy (see Blow and Binstock, "How to find the inertia
+/// tensor (or other mass properties) of a 3D solid body represented by a triangle mesh").
+pub(crate) fn mass_properties(triangles: &[Triangle]) -> MassProperties {
+    let reference = triangles
+        .first()
+        .map_or(Vector3::zeros(), |t| t.0.coords.map(f64::from));
+    // Second moments of the canonical tetrahedron with vertices at the origin and unit distance along each axis
+    let canonical = Matrix3::new(2., 1., 1., 1., 2., 1., 1., 1., 2.) / 120.;
+    let (volume, moment, covariance) = triangles.iter().fold(
+        (0., Vector3::zeros(), Matrix3::zeros()),
+        |(volume, moment, covariance): (f64, Vector3<f64>, Matrix3<f64>), (a, b, c)| {
+            let corners = Matrix3::from_columns(&[
+                a.coords.map(f64::from) - reference,
+                b.coords.map(f64::from) - reference,
+                c.coords.map(f64::from) - reference,
+            ]);
+            let det = corners.determinant();
+            (
+                volume + det / 6.,
+                moment + corners.column_sum() * (det / 24.),
+                covariance + corners * canonical * corners.transpose() * det,
+            )
+        },
+    );
+    if volume == 0. {
+        return MassProperties {
+            volume: 0.,
+            centroid: Point3::from(reference.map(|x| x as f32)),
+            inertia: Matrix3::zeros(),
+        };
+    }
+    let offset = moment / volume;
+    // Move the second moments from the reference vertex to the centroid, then convert to an inertia tensor
+    let covariance = (covariance - offset * offset.transpose() * volume) * volume.signum();
+    let inertia = Matrix3::identity() * covariance.trace() - covariance;
+    MassProperties {
+        volume: volume as f32,
+        centroid: Point3::from((reference + offset).map(|x| x as f32)),
+        inertia: inertia.map(|x| x as f32),
+    }
+}
+
+/// Collects the corners of each triangle into a list of vertices, merging corners at identical locations, and the
+/// indices of the vertices making up each face.
+pub(crate) fn index_triangles<'a, I>(triangles: I) -> (Vec<Point3<f32>>, Vec<[usize; 3]>)
+where
+    I: IntoIterator<Item = &'a Triangle>,
+{
+    let mut vertices = Vec::new();
+    let mut indices = HashMap::new();
+    let faces = triangles
+        .into_iter()
+        .map(|&(a, b, c)| {
+            let mut face = [0; 3];
+            for (idx, corner) in face.iter_mut().zip([a, b, c].iter()) {
+                // Treat -0 and 0 as the same location
+                let key = [corner.x + 0., corner.y + 0., corner.z + 0.].map(f32::to_bits);
+                *idx = *indices.entry(key).or_insert_with(|| {
+                    vertices.push(*corner);
+                    vertices.len() - 1
+                });
+            }
+            face
+        })
+        .collect();
+    (vertices, faces)
+}
+
+/// Checks that `faces` index three distinct vertices out of `vertex_count`, and together form a closed, consistently
+/// wound surface: every edge must be shared by exactly two faces which run along it in opposite directions.
+pub(crate) fn check_faces(faces: &[[usize; 3]], vertex_count: usize) -> Result<(), Error> {
+    if faces.iter().any(|&[a, b, c]| {
+        a == b || b == c || c == a || [a, b, c].iter().any(|&idx| idx >= vertex_count)
+    }) {
+        return Err(Error::InvalidFace);
+    }
+    if faces.is_empty() {
+        return Err(Error::OpenMesh);
+    }
+
+    // Count how many faces run along each directed edge
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for &[a, b, c] in faces.iter() {
+        for &edge in [(a, b), (b, c), (c, a)].iter() {
+            *edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+    let along = |edge: &(usize, usize)| edges.get(edge).copied().unwrap_or(0);
+    if edges
+        .keys()
+        .any(|&(a, b)| along(&(a, b)) + along(&(b, a)) != 2)
+    {
+        return Err(Error::OpenMesh);
+    }
+    if edges.values().any(|&count| count != 1) {
+        return Err(Error::InconsistentOrientation);
+    }
+    Ok(())
+}
+
+/// The smallest box enclosing every triangle of a trimesh, or `None` if there are no triangles.
+pub fn trimesh_bounds(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+) -> Option<(Point3<f32>, Point3<f32>)> {
+    let first = triangles.first()?.0;
+    Some(
+        triangles
+            .iter()
+            .fold((first, first), |(min, max), (a, b, c)| {
+                (min.inf(a).inf(b).inf(c), max.sup(a).sup(b).sup(c))
+            }),
+    )
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+/// The size a trimesh is scaled to by `normalize_trimesh`.
+pub enum MeshSize {
+    /// Keep the trimesh at its current size.
+    Unchanged,
+    /// Scale so the trimesh encloses this volume.
+    Volume(f32),
+    /// Scale so the longest side of the trimesh's bounding box has this length.
+    Extent(f32),
+}
+
+/// The uniform scale factor bringing a trimesh to `size`. Fails if `size` is not positive, or the trimesh has no
+/// volume or extent to scale.
+pub fn normalizing_scale(
+    triangles: &[(Point3<f32>, Point3<f32>, Point3<f32>)],
+    size: MeshSize,
+) -> Result<f32, Error> {
+    let (target, current, power) = match size {
+        MeshSize::Unchanged => return Ok(1.),
+        MeshSize::Volume(volume) => (volume, mass_properties(triangles).volume.abs(), 3),
+        MeshSize::Extent(extent) => (
+            extent,
+            trimesh_bounds(triangles).map_or(0., |(min, max)| (max - min).max()),
+            1,
+        ),
+    };
+    if target <= 0. {
+        Err(Error::NegativeScale)
+    } else if current <= 0. {
+        Err(Error::DegenerateMesh)
+    } else {
+        Ok((target / current).powf(1. / power as f32))
+    }
+}
+
+/// Returns a copy of a trimesh translated so that `center` sits at the origin, then scaled uniformly about it to
+/// `size`. Choosing a `center` inside the trimesh, such as its `trimesh_centroid`, lets packing start from the origin.
+pub fn normalize_trimesh(
+    triangles: &[Triangle],
+    center: &Point3<f32>,
+    size: MeshSize,
+) -> Result<Vec<Triangle>, Error> {
+    let scale = normalizing_scale(triangles, size)?;
+    let place = |point: &Point3<f32>| Point3::from((point - center) * scale);
+    Ok(triangles
+        .iter()
+        .map(|(a, b, c)| (place(a), place(b), place(c)))
+        .collect())
+}
+
+/// Estimates the volume of the region within the box spanning `min` to `max` for which `inside`
+/// holds, from `samples` uniformly distributed points. Points are drawn from a fixed seed, so the
+/// same region always yields the same estimate.
+pub fn monte_carlo_volume<F: Fn(&Point3<f32>) -> bool>(
+    min: &Point3<f32>,
+    max: &Point3<f32>,
+    samples: usize,
+    inside: F,
+) -> f32 {
+    let extents = max - min;
+    if samples == 0 || extents.iter().any(|extent| *extent <= 0.) {
+        return 0.;
+    }
+    let mut rng = StdRng::seed_from_u64(0);
+    let hits = (0..samples)
+        .filter(|_| {
+            let point = min + extents.map(|extent| extent * rng.gen::<f32>());
+            inside(&point)
+        })
+        .count();
+    extents.x * extents.y * extents.z * hits as f32 / samples as f32
+}
+
+#[test]
+fn closest_point_regions() {
+    let triangle = (
+        Point3::origin(),
+        Point3::new(2., 0., 0.),
+        Point3::new(0., 2., 0.),
+    );
+    // Above the face
+    assert_eq!(
+        closest_point_on_triangle(&Point3::new(0.5, 0.5, 3.), &triangle),
+        Point3::new(0.5, 0.5, 0.)
+    );
+    // Beyond a vertex
+    assert_eq!(
+        closest_point_on_triangle(&Point3::new(-1., -1., 1.), &triangle),
+        Point3::origin()
+    );
+    // Beyond the hypotenuse
+    assert_eq!(
+        closest_point_on_triangle(&Point3::new(2., 2., 0.), &triangle),
+        Point3::new(1., 1., 0.)
+    );
+    assert!((point_triangle_distance(&Point3::new(1., -2., 0.), &triangle) - 2.).abs() < 1e-6);
+}
+
+#[test]
+fn winding_number_tetrahedron() {
+    let (o, x, y, z) = (
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(0., 0., 1.),
+    );
+    let triangles = [(o, y, x), (o, x, z), (x, y, z), (o, z, y)];
+
+    assert!((winding_number(&triangles, &Point3::new(0.1, 0.1, 0.1)) - 1.).abs() < 1e-6);
+    assert!(winding_number(&triangles, &Point3::new(1., 1., 1.)).abs() < 1e-6);
+    // On a face, an edge where faces meet at right angles, and a corner with three right angles
+    assert!((winding_number(&triangles, &Point3::new(0.2, 0.2, 0.)) - 0.5).abs() < 1e-6);
+    assert!((winding_number(&triangles, &Point3::new(0.5, 0., 0.)) - 0.25).abs() < 1e-6);
+    assert!((winding_number(&triangles, &o) - 0.125).abs() < 1e-6);
+
+    // A ray from inside through the edge at x = 0.5 grazes it, but the inside test is not fooled
+    let inside = Point3::new(0.25, 0.1, 0.1);
+    let dir = Point3::new(0.5, 0., 0.) - inside;
+    assert_eq!(robust_ray_crossing_count(&triangles, &inside, &dir), None);
+    assert!(point_in_trimesh(&triangles, &inside));
+}
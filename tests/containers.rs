@@ -1,15 +1,24 @@
 extern crate float_cmp;
 extern crate nalgebra;
 extern crate obj;
+extern crate rand;
 extern crate spherical_cow;
 
 use float_cmp::ApproxEqUlps;
-use nalgebra::{Matrix, Point3};
+use nalgebra::{Isometry3, Matrix, Matrix3, Point3, Vector3};
 use obj::Obj;
+use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use spherical_cow::errors::SphericalCowError;
 use spherical_cow::shapes::*;
-use spherical_cow::util::{ray_intersection_count, trimesh_volume};
-use spherical_cow::Container;
-use std::f32::consts::PI;
+use spherical_cow::util::{
+    normalize_trimesh, point_in_trimesh, ray_intersection_count, robust_ray_crossing_count,
+    trimesh_bounds, trimesh_centroid, trimesh_properties, trimesh_volume, winding_number, MeshSize,
+};
+use spherical_cow::{pack_spheres_with_rng, Container, Disjoint};
+use std::collections::BTreeMap;
+use std::f32::consts::{FRAC_PI_2, PI};
 use std::path::Path;
 
 #[test]
@@ -68,7 +77,7 @@ struct Emerald {
 
 impl Emerald {
     fn build() -> Emerald {
-        let emerald = Obj::load(&Path::new("examples/objects/emerald.obj")).unwrap();
+        let emerald = Obj::load(Path::new("examples/objects/emerald.obj")).unwrap();
         let points: Vec<Point3<f32>> = emerald
             .data
             .position
@@ -88,9 +97,7 @@ impl Emerald {
                 }
             }
         }
-        Emerald {
-            triangles: triangles,
-        }
+        Emerald { triangles }
     }
 }
 
@@ -133,3 +140,908 @@ fn emerald_volume() {
 
     assert!(emerald.volume().approx_eq_ulps(&2354.709, 2));
 }
+
+fn load_mesh(path: &str) -> TriMesh {
+    let obj = Obj::load(Path::new(path)).unwrap();
+    let vertices = obj
+        .data
+        .position
+        .iter()
+        .map(|pos| Point3::new(pos[0], pos[1], pos[2]))
+        .collect();
+
+    let mut faces = Vec::new();
+    for object in obj.data.objects.iter() {
+        for group in object.groups.iter() {
+            for poly in group.polys.iter() {
+                faces.push([poly.0[0].0, poly.0[1].0, poly.0[2].0]);
+            }
+        }
+    }
+    TriMesh::new(vertices, faces).unwrap()
+}
+
+/// Vertices and faces of a cube with half extent `half` about `center`, wound with
+/// outward normals. The last two faces make up the top of the cube.
+fn cube_parts(center: Point3<f32>, half: f32) -> (Vec<Point3<f32>>, Vec<[usize; 3]>) {
+    let vertices = (0..8)
+        .map(|idx| {
+            let corner = |bit: usize| if idx & bit == 0 { -half } else { half };
+            center + Vector3::new(corner(1), corner(2), corner(4))
+        })
+        .collect();
+    let faces = vec![
+        [0, 2, 1],
+        [1, 2, 3],
+        [0, 1, 4],
+        [1, 5, 4],
+        [2, 6, 3],
+        [3, 6, 7],
+        [0, 4, 2],
+        [2, 4, 6],
+        [1, 3, 5],
+        [3, 7, 5],
+        [4, 5, 6],
+        [5, 7, 6],
+    ];
+    (vertices, faces)
+}
+
+fn cube_mesh(center: Point3<f32>, half: f32) -> TriMesh {
+    let (vertices, faces) = cube_parts(center, half);
+    TriMesh::new(vertices, faces).unwrap()
+}
+
+#[test]
+fn in_cube_mesh() {
+    let cube = cube_mesh(Point3::origin(), 1.);
+
+    assert!(cube.volume().approx_eq_ulps(&8., 2));
+    assert!(cube.contains(&Sphere::new(Point3::new(0.35, 0.35, 0.), 0.6).unwrap()));
+    // A ray from the origin through the center leaves by a corner, but the sphere
+    // pokes through the neighbouring faces.
+    assert!(!cube.contains(&Sphere::new(Point3::new(0.5, 0.5, 0.5), 0.6).unwrap()));
+    assert!(!cube.contains(&Sphere::new(Point3::new(0.5, 0.5, 0.), 0.6).unwrap()));
+}
+
+#[test]
+fn in_cube_mesh_away_from_origin() {
+    let cube = cube_mesh(Point3::new(5., 5., 5.), 1.);
+
+    assert!(cube.contains(&Sphere::new(Point3::new(5., 5., 5.), 0.9).unwrap()));
+    assert!(!cube.contains(&Sphere::new(Point3::new(5., 5., 5.), 1.1).unwrap()));
+    assert!(!cube.contains(&Sphere::new(Point3::new(3., 5., 5.), 0.5).unwrap()));
+    assert!(cube.is_disjoint(&Sphere::new(Point3::new(3., 5., 5.), 0.5).unwrap()));
+    assert!(!cube.is_disjoint(&Sphere::new(Point3::new(3., 5., 5.), 1.5).unwrap()));
+}
+
+#[test]
+fn normalize_cube_mesh() {
+    let cube = cube_mesh(Point3::new(5., 5., 5.), 1.);
+    let centroid = trimesh_centroid(cube.triangles());
+    assert!((centroid - Point3::new(5., 5., 5.)).norm() < 1e-5);
+    assert_eq!(cube.centroid(), centroid);
+    assert_eq!(
+        trimesh_bounds(cube.triangles()),
+        Some((Point3::new(4., 4., 4.), Point3::new(6., 6., 6.)))
+    );
+
+    let unit = normalize_trimesh(cube.triangles(), &centroid, MeshSize::Volume(1.)).unwrap();
+    assert!((trimesh_volume(&unit) - 1.).abs() < 1e-5);
+    let (min, max) = trimesh_bounds(&unit).unwrap();
+    assert!((min - Point3::new(-0.5, -0.5, -0.5)).norm() < 1e-5);
+    assert!((max - Point3::new(0.5, 0.5, 0.5)).norm() < 1e-5);
+
+    let wide = cube
+        .normalized(&Point3::new(4., 5., 5.), MeshSize::Extent(4.))
+        .unwrap();
+    assert_eq!(
+        wide.bounding_box(),
+        Some((Point3::new(0., -2., -2.), Point3::new(4., 2., 2.)))
+    );
+    assert!(cube.normalized(&centroid, MeshSize::Volume(-1.)).is_err());
+}
+
+#[test]
+fn normalize_degenerate_mesh() {
+    let (o, x, y) = (
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+    );
+    // Both sides of a single triangle: closed, but flat
+    let flat = [(o, x, y), (o, y, x)];
+
+    let err = normalize_trimesh(&flat, &o, MeshSize::Volume(1.)).unwrap_err();
+    assert!(matches!(err, SphericalCowError::DegenerateMesh));
+    let err = normalize_trimesh(&[], &o, MeshSize::Extent(1.)).unwrap_err();
+    assert!(matches!(err, SphericalCowError::DegenerateMesh));
+    let err = normalize_trimesh(&flat, &o, MeshSize::Volume(-1.)).unwrap_err();
+    assert!(matches!(err, SphericalCowError::NegativeScale));
+    assert!(normalize_trimesh(&flat, &o, MeshSize::Extent(2.)).is_ok());
+}
+
+#[test]
+fn emerald_centroid_inside() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let centroid = emerald.centroid();
+
+    assert!(emerald.contains(&Sphere::new(centroid, 1.).unwrap()));
+    let centred = emerald.normalized(&centroid, MeshSize::Unchanged).unwrap();
+    assert!(centred.centroid().coords.norm() < 1e-3);
+    assert!((centred.volume() - emerald.volume()).abs() < 1e-2);
+}
+
+#[test]
+fn box_mass_properties_far_from_origin() {
+    // A 2 x 4 x 6 box, well away from the origin
+    let (vertices, faces) = cube_parts(Point3::origin(), 1.);
+    let center = Vector3::new(1000., -2000., 500.);
+    let vertices: Vec<Point3<f32>> = vertices
+        .iter()
+        .map(|v| Point3::new(v.x, 2. * v.y, 3. * v.z) + center)
+        .collect();
+    let triangles: Vec<_> = faces
+        .iter()
+        .map(|&[a, b, c]| (vertices[a], vertices[b], vertices[c]))
+        .collect();
+
+    let properties = trimesh_properties(&triangles).unwrap();
+    assert!((properties.volume - 48.).abs() < 1e-4);
+    assert!((properties.centroid - Point3::from(center)).norm() < 1e-3);
+    let expected = Vector3::new(16. + 36., 4. + 36., 4. + 16.) * 48. / 12.;
+    assert!((properties.inertia.diagonal() - expected).norm() < 1e-2);
+    assert!((properties.inertia - Matrix3::from_diagonal(&expected)).norm() < 1e-2);
+
+    // Winding the other way round leaves everything unchanged
+    let inward: Vec<_> = triangles.iter().map(|&(a, b, c)| (a, c, b)).collect();
+    let flipped = trimesh_properties(&inward).unwrap();
+    assert!((flipped.volume - properties.volume).abs() < 1e-4);
+    assert!((flipped.inertia - properties.inertia).norm() < 1e-2);
+
+    let mesh = TriMesh::new(vertices, faces).unwrap();
+    assert_eq!(mesh.centroid(), properties.centroid);
+    assert_eq!(mesh.inertia(), properties.inertia);
+}
+
+#[test]
+fn trimesh_properties_errors() {
+    let cube = cube_mesh(Point3::origin(), 1.);
+    let mut triangles = cube.triangles().to_vec();
+
+    triangles.pop();
+    assert!(matches!(
+        trimesh_properties(&triangles),
+        Err(SphericalCowError::OpenMesh)
+    ));
+
+    let (a, b, c) = cube.triangles()[11];
+    triangles.push((a, c, b));
+    assert!(matches!(
+        trimesh_properties(&triangles),
+        Err(SphericalCowError::InconsistentOrientation)
+    ));
+}
+
+#[test]
+fn trimesh_without_volume() {
+    let vertices = vec![
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+    ];
+    // Both sides of a single triangle: closed and consistently wound, but flat
+    let faces = vec![[0, 1, 2], [0, 2, 1]];
+    assert!(matches!(
+        TriMesh::new(vertices, faces),
+        Err(SphericalCowError::DegenerateMesh)
+    ));
+}
+
+/// Midpoint and mean outward normal of each edge of a mesh, along with the largest
+/// distance a point may be moved from the edge without leaving its two faces behind.
+fn mesh_edges(mesh: &TriMesh) -> Vec<(Point3<f32>, Vector3<f32>, f32)> {
+    let vertices = mesh.vertices();
+    let mut normals: BTreeMap<(usize, usize), Vector3<f32>> = BTreeMap::new();
+    for &[a, b, c] in mesh.faces().iter() {
+        let normal = (vertices[b] - vertices[a])
+            .cross(&(vertices[c] - vertices[a]))
+            .normalize();
+        for &(p, q) in [(a, b), (b, c), (c, a)].iter() {
+            *normals
+                .entry((p.min(q), p.max(q)))
+                .or_insert_with(Vector3::zeros) += normal;
+        }
+    }
+    normals
+        .iter()
+        .map(|(&(p, q), normal)| {
+            let length = (vertices[q] - vertices[p]).norm();
+            (
+                nalgebra::center(&vertices[p], &vertices[q]),
+                normal.normalize(),
+                length,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn points_beside_emerald_edges() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let triangles = emerald.triangles();
+    for (midpoint, normal, length) in mesh_edges(&emerald) {
+        let inside = midpoint - normal * length * 1e-2;
+        let outside = midpoint + normal * length * 1e-2;
+        assert!(point_in_trimesh(triangles, &inside));
+        assert!(!point_in_trimesh(triangles, &outside));
+        assert!(emerald.bvh().contains_point(&inside));
+        assert!(!emerald.bvh().contains_point(&outside));
+
+        // A ray aimed straight through the edge can't be trusted to count it once
+        let through = midpoint - inside;
+        assert_eq!(
+            robust_ray_crossing_count(triangles, &inside, &through),
+            None
+        );
+
+        // Exactly on the edge the point is on neither side
+        let winding = winding_number(triangles, &midpoint);
+        assert!(winding > 1e-3 && winding < 1. - 1e-3);
+        let touching = Sphere::new(midpoint, 1e-3).unwrap();
+        assert!(!emerald.contains(&touching));
+        assert!(!emerald.is_disjoint(&touching));
+    }
+    for vertex in emerald.vertices().iter() {
+        let winding = winding_number(triangles, vertex);
+        assert!(winding > 1e-3 && winding < 1. - 1e-3);
+    }
+}
+
+#[test]
+fn points_beside_cow_edges() {
+    // The cow passes through itself in places, where the ray casts count the faces crossed rather
+    // than following the local normal. Both agree with the parity of the winding number.
+    let cow = load_mesh("examples/objects/cow.obj");
+    let triangles = cow.triangles();
+    // Winding numbers visit every face, so only some edges are checked
+    for (midpoint, normal, length) in mesh_edges(&cow).into_iter().step_by(200) {
+        for &offset in [-1e-2, 1e-2].iter() {
+            let point = midpoint + normal * length * offset;
+            let inside = winding_number(triangles, &point).round() as i64 % 2 != 0;
+            assert_eq!(cow.bvh().contains_point(&point), inside);
+            assert_eq!(point_in_trimesh(triangles, &point), inside);
+        }
+        let touching = Sphere::new(midpoint, length * 1e-3).unwrap();
+        assert!(!cow.contains(&touching));
+    }
+}
+
+#[test]
+fn in_concave_mesh() {
+    // Unit cube with its top pushed down into a pyramid shaped dent reaching z = 0.2
+    let (mut vertices, mut faces) = cube_parts(Point3::origin(), 1.);
+    vertices.push(Point3::new(0., 0., 0.2));
+    faces.truncate(10);
+    faces.extend_from_slice(&[[4, 5, 8], [5, 7, 8], [7, 6, 8], [6, 4, 8]]);
+    let dented = TriMesh::new(vertices, faces).unwrap();
+
+    assert!((dented.volume() - (8. - 4. * 0.8 / 3.)).abs() < 1e-5);
+    assert!(dented.contains(&Sphere::new(Point3::new(0.6, 0., 0.3), 0.25).unwrap()));
+    // A ray from the origin through this center never meets the dent, yet the sphere
+    // pokes through its side.
+    assert!(!dented.contains(&Sphere::new(Point3::new(0.6, 0., 0.3), 0.3).unwrap()));
+    // Inside the cube's outline, but above the dent
+    assert!(!dented.contains(&Sphere::new(Point3::new(0.1, 0.1, 0.9), 0.05).unwrap()));
+    assert!(dented.is_disjoint(&Sphere::new(Point3::new(0.1, 0.1, 0.9), 0.05).unwrap()));
+}
+
+#[test]
+fn in_emerald_trimesh() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let contents = Sphere::new(Point3::new(2.1, 1.5, 0.8), 2.0).unwrap();
+
+    assert!(emerald.contains(&contents));
+}
+
+#[test]
+fn outside_emerald_trimesh() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let contents = Sphere::new(Point3::new(2.1, 6.5, 3.8), 1.0).unwrap();
+
+    assert!(!emerald.contains(&contents));
+}
+
+#[test]
+fn emerald_trimesh_volume() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+
+    assert!(emerald.volume().approx_eq_ulps(&2354.709, 2));
+}
+
+#[test]
+fn emerald_bounds_vertices() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let (min, max) = emerald.bounding_box().unwrap();
+
+    assert!(emerald.vertices().iter().all(|v| v >= &min && v <= &max));
+    assert!(emerald.vertices().iter().any(|v| v.x == min.x));
+    assert!(emerald.vertices().iter().any(|v| v.z == max.z));
+}
+
+#[test]
+fn cow_mesh_is_closed() {
+    let cow = load_mesh("examples/objects/cow.obj");
+
+    assert_eq!(cow.faces().len(), 5804);
+    assert!(cow.volume() > 0.);
+}
+
+#[test]
+fn trimesh_rejects_holes() {
+    let emerald = load_mesh("examples/objects/emerald.obj");
+    let vertices = emerald.vertices().to_vec();
+    let faces = emerald.faces()[1..].to_vec();
+
+    assert!(TriMesh::new(vertices, faces).is_err());
+}
+
+#[test]
+fn in_periodic_cuboid() {
+    let container = PeriodicCuboid::new(5.0, 10.0, 5.0).unwrap();
+    // Spheres may cross the faces of a periodic cell, so long as their center is inside
+    let contents = Sphere::new(Point3::new(4.8, 9.0, -4.9), 1.0).unwrap();
+
+    assert!(container.contains(&contents));
+}
+
+#[test]
+fn outside_periodic_cuboid() {
+    let container = PeriodicCuboid::new(5.0, 10.0, 5.0).unwrap();
+    let contents = Sphere::new(Point3::new(5.2, 9.0, 3.8), 1.0).unwrap();
+
+    assert!(!container.contains(&contents));
+}
+
+#[test]
+fn periodic_cuboid_volume_and_period() {
+    let cuboid = PeriodicCuboid::new(15.2, 8.0, 12.3).unwrap();
+
+    assert!(cuboid
+        .volume()
+        .approx_eq_ulps(&((2. * 15.2) * (2. * 8.) * (2. * 12.3)), 2));
+    assert_eq!(cuboid.period(), Some(Vector3::new(30.4, 16.0, 24.6)));
+    assert_eq!(Cuboid::new(15.2, 8.0, 12.3).unwrap().period(), None);
+}
+
+#[test]
+fn in_offset_sphere() {
+    let container = Sphere::new(Point3::new(30., -12., 5.), 4.0).unwrap();
+    let contents = Sphere::new(Point3::new(31., -10., 6.), 1.0).unwrap();
+    let at_origin = Sphere::new(Point3::origin(), 1.0).unwrap();
+
+    assert!(container.contains(&contents));
+    assert!(!container.contains(&at_origin));
+    assert_eq!(container.seed_point(), Point3::new(30., -12., 5.));
+}
+
+#[test]
+fn bounding_boxes() {
+    let sphere = Sphere::new(Point3::new(30., -12., 5.), 4.0).unwrap();
+    assert_eq!(
+        sphere.bounding_box(),
+        Some((Point3::new(26., -16., 1.), Point3::new(34., -8., 9.)))
+    );
+
+    let cuboid = Cuboid::new(1.5, 2.0, 2.5).unwrap();
+    assert_eq!(
+        cuboid.bounding_box(),
+        Some((Point3::new(-1.5, -2.0, -2.5), Point3::new(1.5, 2.0, 2.5)))
+    );
+}
+
+#[test]
+fn in_oriented_cuboid() {
+    // A long box along x, turned a quarter turn about z so it runs along y, then moved
+    let isometry = Isometry3::new(Vector3::new(10., 0., -5.), Vector3::z() * FRAC_PI_2);
+    let container = Cuboid::new(4., 1., 1.).unwrap().with_isometry(isometry);
+
+    let along_y = Sphere::new(Point3::new(10., 3., -5.), 0.5).unwrap();
+    let along_x = Sphere::new(Point3::new(13., 0., -5.), 0.5).unwrap();
+    let at_origin = Sphere::new(Point3::origin(), 0.5).unwrap();
+
+    assert!(container.contains(&along_y));
+    assert!(!container.contains(&along_x));
+    assert!(!container.contains(&at_origin));
+    assert!(container.volume().approx_eq_ulps(&32., 2));
+    assert_eq!(container.seed_point(), Point3::new(10., 0., -5.));
+
+    let (min, max) = container.bounding_box().unwrap();
+    assert!((min - Point3::new(9., -4., -6.)).norm() < 1e-5);
+    assert!((max - Point3::new(11., 4., -4.)).norm() < 1e-5);
+}
+
+#[test]
+fn in_transformed_containers() {
+    let isometry = Isometry3::new(Vector3::new(-3., 4., 1.), Vector3::x() * FRAC_PI_2);
+    // Unit sphere grown to radius 2
+    let sphere = Sphere::new(Point3::origin(), 1.).unwrap();
+    let container = Transformed::new(sphere, isometry, 2.).unwrap();
+
+    let inside = Sphere::new(Point3::new(-3., 4., 2.), 0.9).unwrap();
+    let too_big = Sphere::new(Point3::new(-3., 4., 2.), 1.1).unwrap();
+    assert!(container.contains(&inside));
+    assert!(!container.contains(&too_big));
+    assert!(container.volume().approx_eq_ulps(&(32. / 3. * PI), 2));
+    assert_eq!(container.seed_point(), Point3::new(-3., 4., 1.));
+
+    // A box which is long along y locally; rotating about x turns it to lie along z
+    let cuboid = Cuboid::new(1., 3., 1.).unwrap();
+    let container = Transformed::new(cuboid, isometry, 0.5).unwrap();
+    let along_z = Sphere::new(Point3::new(-3., 4., 2.), 0.4).unwrap();
+    let along_y = Sphere::new(Point3::new(-3., 5., 1.), 0.4).unwrap();
+    assert!(container.contains(&along_z));
+    assert!(!container.contains(&along_y));
+    assert!(container.volume().approx_eq_ulps(&3., 2));
+
+    let (min, max) = container.bounding_box().unwrap();
+    assert!((min - Point3::new(-3.5, 3.5, -0.5)).norm() < 1e-5);
+    assert!((max - Point3::new(-2.5, 4.5, 2.5)).norm() < 1e-5);
+}
+
+#[test]
+fn cylinder_volume() {
+    let cylinder = Cylinder::new(1.5, 4.).unwrap();
+    assert!(cylinder.volume().approx_eq_ulps(&(9. * PI), 2));
+}
+
+#[test]
+fn in_cylinder() {
+    let cylinder = Cylinder::new(2., 6.).unwrap();
+
+    // Touching the wall and an end cap
+    let snug = Sphere::new(Point3::new(1., 0., 2.), 1.).unwrap();
+    // Fits within the radius and height separately, but not in the corner of the two
+    let corner = Sphere::new(Point3::new(1.2, 1.2, 2.5), 0.4).unwrap();
+    let past_cap = Sphere::new(Point3::new(0., 0., 2.8), 0.5).unwrap();
+
+    assert!(cylinder.contains(&snug));
+    assert!(!cylinder.contains(&corner));
+    assert!(!cylinder.contains(&past_cap));
+}
+
+#[test]
+fn in_transformed_cylinder() {
+    // Lay the cylinder along x, centered away from the origin
+    let isometry = Isometry3::new(Vector3::new(5., 5., 5.), Vector3::y() * FRAC_PI_2);
+    let cylinder = Cylinder::new(1., 8.).unwrap().with_isometry(isometry);
+
+    let along_x = Sphere::new(Point3::new(8., 5., 5.), 0.9).unwrap();
+    let along_z = Sphere::new(Point3::new(5., 5., 8.), 0.9).unwrap();
+    assert!(cylinder.contains(&along_x));
+    assert!(!cylinder.contains(&along_z));
+    assert_eq!(cylinder.seed_point(), Point3::new(5., 5., 5.));
+
+    let (min, max) = cylinder.bounding_box().unwrap();
+    assert!((min - Point3::new(1., 4., 4.)).norm() < 1e-5);
+    assert!((max - Point3::new(9., 6., 6.)).norm() < 1e-5);
+}
+
+#[test]
+fn capsule_volume() {
+    let capsule = Capsule::new(1., 2.).unwrap();
+    assert!(capsule.volume().approx_eq_ulps(&(10. / 3. * PI), 2));
+}
+
+#[test]
+fn in_capsule() {
+    let capsule = Capsule::new(1., 2.).unwrap();
+
+    let in_cap = Sphere::new(Point3::new(0., 0., 1.5), 0.49).unwrap();
+    let in_shaft = Sphere::new(Point3::new(0.5, 0., -0.8), 0.49).unwrap();
+    // Within the capsule's bounding cylinder, but not its rounded cap
+    let cap_corner = Sphere::new(Point3::new(0.5, 0., 1.5), 0.3).unwrap();
+
+    assert!(capsule.contains(&in_cap));
+    assert!(capsule.contains(&in_shaft));
+    assert!(!capsule.contains(&cap_corner));
+}
+
+#[test]
+fn cone_volume() {
+    let cone = Cone::new(2., 4.).unwrap();
+    assert!(cone.volume().approx_eq_ulps(&(16. / 3. * PI), 2));
+}
+
+#[test]
+fn in_cone() {
+    // Base at z = -2, apex at z = 2. The slanted wall is 6/√20 from (0, 0, -1)
+    // and 4/√20 from the origin.
+    let cone = Cone::new(2., 4.).unwrap();
+
+    assert!(cone.contains(&Sphere::new(Point3::new(0., 0., -1.), 0.99).unwrap()));
+    assert!(!cone.contains(&Sphere::new(Point3::new(0., 0., -1.), 1.01).unwrap()));
+    assert!(cone.contains(&Sphere::new(Point3::origin(), 0.89).unwrap()));
+    assert!(!cone.contains(&Sphere::new(Point3::origin(), 0.9).unwrap()));
+    // Center outside the cone entirely
+    assert!(!cone.contains(&Sphere::new(Point3::new(1.5, 0., 1.5), 0.01).unwrap()));
+    assert!(cone.contains(&Sphere::new(cone.seed_point(), 0.5).unwrap()));
+}
+
+#[test]
+fn frustum_volume() {
+    let frustum = Frustum::new(2., 1., 2.).unwrap();
+    assert!(frustum.volume().approx_eq_ulps(&(14. / 3. * PI), 2));
+}
+
+#[test]
+fn in_frustum() {
+    // Faces at z = ±1, slanted wall 3/√5 from the origin
+    let frustum = Frustum::new(2., 1., 2.).unwrap();
+
+    assert!(frustum.contains(&Sphere::new(Point3::origin(), 0.99).unwrap()));
+    assert!(!frustum.contains(&Sphere::new(Point3::origin(), 1.01).unwrap()));
+    // Off axis, the slanted wall is 0.6/√5 away
+    assert!(frustum.contains(&Sphere::new(Point3::new(0., 1.2, 0.), 0.26).unwrap()));
+    assert!(!frustum.contains(&Sphere::new(Point3::new(0., 1.2, 0.), 0.28).unwrap()));
+
+    // Flipped upside down and lifted, the wide face is now on top
+    let isometry = Isometry3::new(Vector3::z() * 10., Vector3::x() * PI);
+    let flipped = frustum.with_isometry(isometry);
+    assert!(flipped.contains(&Sphere::new(Point3::new(1.5, 0., 10.9), 0.05).unwrap()));
+    assert!(!flipped.contains(&Sphere::new(Point3::new(1.5, 0., 9.1), 0.05).unwrap()));
+}
+
+#[test]
+fn ellipsoid_volume() {
+    let ellipsoid = Ellipsoid::new(3., 2., 1.).unwrap();
+    assert!(ellipsoid.volume().approx_eq_ulps(&(8. * PI), 2));
+}
+
+#[test]
+fn ellipsoid_matches_sphere() {
+    let ellipsoid = Ellipsoid::new(2., 2., 2.).unwrap();
+    let sphere = Sphere::new(Point3::origin(), 2.).unwrap();
+    for &(x, radius) in [(0., 1.99), (0., 2.01), (1., 0.99), (1., 1.01), (1.9, 0.2)].iter() {
+        let ball = Sphere::new(Point3::new(x, 0., 0.), radius).unwrap();
+        assert_eq!(ellipsoid.contains(&ball), sphere.contains(&ball));
+    }
+}
+
+#[test]
+fn in_ellipsoid_sampled() {
+    // Densely sample the surface: the closest sample is never nearer than the true
+    // distance to the surface, and at most half a sample spacing further away.
+    let semi_axes = Vector3::new(3f32, 1.5, 0.5);
+    let ellipsoid = Ellipsoid::new(semi_axes.x, semi_axes.y, semi_axes.z).unwrap();
+    let (rings, segments) = (200, 400);
+    let mut surface = Vec::with_capacity(rings * segments);
+    for ring in 0..=rings {
+        let theta = PI * ring as f32 / rings as f32;
+        for segment in 0..segments {
+            let phi = 2. * PI * segment as f32 / segments as f32;
+            surface.push(Point3::new(
+                semi_axes.x * theta.sin() * phi.cos(),
+                semi_axes.y * theta.sin() * phi.sin(),
+                semi_axes.z * theta.cos(),
+            ));
+        }
+    }
+    let tolerance = 0.5 * semi_axes.x * 2. * PI / segments as f32;
+
+    // Also placed elsewhere and turned, which must not change anything
+    let isometry = Isometry3::new(Vector3::new(4., -1., 2.), Vector3::new(0.3, -0.7, 1.1));
+    let moved = ellipsoid.clone().with_isometry(isometry);
+
+    // Points on the principal planes and axes take separate paths, so include a few
+    let mut centers = vec![
+        Point3::origin(),
+        Point3::new(2.5, 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(0., 0., 0.2),
+        Point3::new(1., 0.5, 0.),
+        Point3::new(0., 0.7, 0.3),
+    ];
+    let mut rng = StdRng::seed_from_u64(42);
+    while centers.len() < 30 {
+        let center = Point3::new(
+            rng.gen_range(-semi_axes.x..semi_axes.x),
+            rng.gen_range(-semi_axes.y..semi_axes.y),
+            rng.gen_range(-semi_axes.z..semi_axes.z),
+        );
+        if center.coords.component_div(&semi_axes).norm() < 1. {
+            centers.push(center);
+        }
+    }
+    for center in centers {
+        let sampled = surface
+            .iter()
+            .map(|point| nalgebra::distance(point, &center))
+            .fold(f32::INFINITY, f32::min);
+        for &radius in [sampled - 2. * tolerance, sampled + 1e-4].iter() {
+            if radius <= 0. {
+                continue;
+            }
+            let ball = Sphere::new(center, radius).unwrap();
+            let expected = radius < sampled;
+            assert_eq!(ellipsoid.contains(&ball), expected, "{}", ball);
+            let moved_ball = Sphere::new(isometry * center, radius).unwrap();
+            assert_eq!(moved.contains(&moved_ball), expected, "{}", moved_ball);
+        }
+    }
+
+    // Centers outside are never contained
+    let outside = Sphere::new(Point3::new(0., 0., 0.6), 0.01).unwrap();
+    assert!(!ellipsoid.contains(&outside));
+}
+
+#[test]
+fn invalid_ellipsoid_contains_nothing() {
+    let ball = Sphere::new(Point3::origin(), 0.1).unwrap();
+    let far = Sphere::new(Point3::new(10., 0., 0.), 0.1).unwrap();
+    for &axis in [f32::NAN, f32::INFINITY, 0., -1.].iter() {
+        let mut ellipsoid = Ellipsoid::new(3., 2., 1.).unwrap();
+        ellipsoid.semi_axes.y = axis;
+        assert!(!ellipsoid.contains(&ball));
+        assert!(!ellipsoid.is_disjoint(&far));
+    }
+}
+
+#[test]
+fn ellipsoid_bounding_box() {
+    // Turned a quarter turn about z, the long axis lies along y
+    let isometry = Isometry3::new(Vector3::new(1., 1., 1.), Vector3::z() * FRAC_PI_2);
+    let ellipsoid = Ellipsoid::new(3., 2., 1.).unwrap().with_isometry(isometry);
+    let (min, max) = ellipsoid.bounding_box().unwrap();
+    assert!((min - Point3::new(-1., -2., 0.)).norm() < 1e-5);
+    assert!((max - Point3::new(3., 4., 2.)).norm() < 1e-5);
+}
+
+#[test]
+fn in_spherical_shell() {
+    let shell = SphericalShell::new(Point3::new(1., 1., 1.), 2., 3.).unwrap();
+
+    let in_wall = Sphere::new(Point3::new(1., 3.5, 1.), 0.5).unwrap();
+    let in_core = Sphere::new(Point3::new(1., 1., 1.), 0.5).unwrap();
+    let across_inner = Sphere::new(Point3::new(3., 1., 1.), 0.5).unwrap();
+    let across_outer = Sphere::new(Point3::new(1., 1., 3.8), 0.5).unwrap();
+
+    assert!(shell.contains(&in_wall));
+    assert!(!shell.contains(&in_core));
+    assert!(!shell.contains(&across_inner));
+    assert!(!shell.contains(&across_outer));
+    assert!(shell.volume().approx_eq_ulps(&(76. / 3. * PI), 2));
+    assert_eq!(shell.seed_point(), Point3::new(3.5, 1., 1.));
+}
+
+#[test]
+fn in_cylindrical_shell() {
+    let shell = CylindricalShell::new(1., 2., 4.).unwrap();
+
+    let in_wall = Sphere::new(Point3::new(0., -1.5, 1.), 0.5).unwrap();
+    let in_core = Sphere::new(Point3::new(0., 0., 1.), 0.5).unwrap();
+    let past_end = Sphere::new(Point3::new(0., -1.5, 1.8), 0.5).unwrap();
+
+    assert!(shell.contains(&in_wall));
+    assert!(!shell.contains(&in_core));
+    assert!(!shell.contains(&past_end));
+    assert!(shell.volume().approx_eq_ulps(&(12. * PI), 2));
+    assert_eq!(shell.seed_point(), Point3::new(1.5, 0., 0.));
+}
+
+#[test]
+fn disjoint_from_shapes() {
+    // Each pair of spheres either just touches the shape from outside, or just reaches into it
+    let isometry = Isometry3::new(Vector3::new(1., 2., 3.), Vector3::new(0.4, -0.2, 0.9));
+    let ball = |x: f32, y: f32, z: f32, radius: f32| {
+        Sphere::new(isometry * Point3::new(x, y, z), radius).unwrap()
+    };
+    let check = |shape: &dyn Disjoint, outside: Sphere, inside: Sphere| {
+        assert!(shape.is_disjoint(&outside), "{}", outside);
+        assert!(!shape.is_disjoint(&inside), "{}", inside);
+    };
+
+    let sphere = Sphere::new(isometry * Point3::origin(), 1.).unwrap();
+    check(&sphere, ball(1.5, 0., 0., 0.49), ball(1.5, 0., 0., 0.51));
+    let cuboid = Cuboid::new(1., 2., 3.).unwrap().with_isometry(isometry);
+    check(&cuboid, ball(1.3, 2.4, 0., 0.49), ball(1.3, 2.4, 0., 0.51));
+    let cylinder = Cylinder::new(1., 2.).unwrap().with_isometry(isometry);
+    check(
+        &cylinder,
+        ball(0., 1.3, 1.4, 0.49),
+        ball(0., 1.3, 1.4, 0.51),
+    );
+    let capsule = Capsule::new(1., 2.).unwrap().with_isometry(isometry);
+    check(&capsule, ball(0., 0., 2.5, 0.49), ball(0., 0., 2.5, 0.51));
+    // Step half a unit out along the normal of the slanted wall, from its midpoint
+    let frustum = Frustum::new(2., 1., 2.).unwrap().with_isometry(isometry);
+    let (y, z) = (1.5 + 1. / 5f32.sqrt(), 0.5 / 5f32.sqrt());
+    check(&frustum, ball(0., y, z, 0.49), ball(0., y, z, 0.51));
+    let cone = Cone::new(1., 2.).unwrap().with_isometry(isometry);
+    check(&cone, ball(0., 0., 1.5, 0.49), ball(0., 0., 1.5, 0.51));
+    let ellipsoid = Ellipsoid::new(3., 2., 1.).unwrap().with_isometry(isometry);
+    check(&ellipsoid, ball(0., 2.5, 0., 0.49), ball(0., 2.5, 0., 0.51));
+    let shell = CylindricalShell::new(1., 2., 2.)
+        .unwrap()
+        .with_isometry(isometry);
+    check(&shell, ball(0., 0.5, 0., 0.49), ball(0., 0.5, 0., 0.51));
+    let shell = SphericalShell::new(isometry * Point3::origin(), 1., 2.).unwrap();
+    check(&shell, ball(0., 2.5, 0., 0.49), ball(0., 2.5, 0., 0.51));
+    let transformed = Transformed::new(Cuboid::new(1., 1., 1.).unwrap(), isometry, 2.).unwrap();
+    check(
+        &transformed,
+        ball(2.5, 0., 0., 0.49),
+        ball(2.5, 0., 0., 0.51),
+    );
+}
+
+#[test]
+fn union_volume() {
+    let one = Sphere::new(Point3::new(-0.5, 0., 0.), 1.).unwrap();
+    let two = Sphere::new(Point3::new(0.5, 0., 0.), 1.).unwrap();
+    let far = Sphere::new(Point3::new(5., 0., 0.), 1.).unwrap();
+
+    // Exact when apart
+    let apart = Union::new(one.clone(), far).unwrap();
+    assert!(apart.volume().approx_eq_ulps(&(8. / 3. * PI), 2));
+
+    // Two unit spheres a unit apart share a lens of 5π/12
+    let joined = Union::new(one.clone(), two.clone()).unwrap();
+    assert!(joined.volume().approx_eq_ulps(&(9. / 4. * PI), 4));
+    let shared = Intersection::new(one.clone(), two.clone()).unwrap();
+    assert!(shared.volume().approx_eq_ulps(&(5. / 12. * PI), 4));
+
+    // Still spheres once transformed, and one wholly inside another shares all of its volume
+    let isometry = Isometry3::new(Vector3::new(-0.5, 0.2, 0.), Vector3::z());
+    let inner =
+        Transformed::new(Sphere::new(Point3::origin(), 1.).unwrap(), isometry, 0.5).unwrap();
+    let cut = Difference::new(one, inner).unwrap();
+    assert!(cut.volume().approx_eq_ulps(&(7. / 6. * PI), 4));
+}
+
+#[test]
+fn combination_volume_with_integrator() {
+    // Half of the sphere lies inside the cube
+    let cube = Cuboid::new(1., 1., 1.).unwrap();
+    let ball = Sphere::new(Point3::new(1., 0., 0.), 1.).unwrap();
+    let half = 2. / 3. * PI;
+
+    let grid = Integrator::Grid { resolution: 100 };
+    let joined = Union::with_integrator(cube.clone(), ball.clone(), grid).unwrap();
+    assert!((joined.volume() - (8. + half)).abs() < 0.01 * half);
+    let shared = Intersection::with_integrator(cube.clone(), ball.clone(), grid).unwrap();
+    assert!((shared.volume() - half).abs() < 0.01 * half);
+
+    // Fewer samples give a rougher, but still sensible, estimate
+    let rough = Integrator::MonteCarlo { samples: 1000 };
+    let cut = Difference::with_integrator(cube, ball, rough).unwrap();
+    assert!((cut.volume() - (8. - half)).abs() < 0.1 * half);
+}
+
+#[test]
+fn union_accepts_seam() {
+    let one = Sphere::new(Point3::new(-0.8, 0., 0.), 1.).unwrap();
+    let two = Sphere::new(Point3::new(0.8, 0., 0.), 1.).unwrap();
+    let joined = Union::new(one.clone(), two.clone()).unwrap();
+
+    // Wholly within the union, but neither sphere on its own
+    let straddling = Sphere::new(Point3::origin(), 0.4).unwrap();
+    assert!(!one.contains(&straddling) && !two.contains(&straddling));
+    assert!(joined.contains(&straddling));
+    // Centered inside, but reaching out where the two surfaces meet
+    assert!(!joined.contains(&Sphere::new(Point3::origin(), 0.7).unwrap()));
+    assert!(!joined.contains(&Sphere::new(Point3::new(0., 0.3, 0.), 0.35).unwrap()));
+
+    // Packing places spheres across the seam, but never outside of the union
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packed = pack_spheres_with_rng(&joined, &mut sizes, &mut StdRng::seed_from_u64(0)).unwrap();
+    assert!(packed
+        .iter()
+        .any(|sphere| !one.contains(sphere) && !two.contains(sphere)));
+    let samples = 200;
+    for sphere in packed.iter() {
+        // Points spread evenly over the surface of each sphere must lie within either
+        for idx in 0..samples {
+            let z = 1. - 2. * (idx as f32 + 0.5) / samples as f32;
+            let phi = idx as f32 * PI * (3. - 5f32.sqrt());
+            let ring = (1. - z * z).sqrt();
+            let point =
+                sphere.center + Vector3::new(ring * phi.cos(), ring * phi.sin(), z) * sphere.radius;
+            assert!(
+                nalgebra::distance(&point, &one.center) <= 1. + 1e-5
+                    || nalgebra::distance(&point, &two.center) <= 1. + 1e-5,
+                "{}",
+                sphere
+            );
+        }
+    }
+}
+
+#[test]
+fn in_union() {
+    let one = Sphere::new(Point3::new(-0.5, 0., 0.), 1.).unwrap();
+    let two = Sphere::new(Point3::new(0.5, 0., 0.), 1.).unwrap();
+    let joined = Union::new(one, two).unwrap();
+
+    assert!(joined.contains(&Sphere::new(Point3::new(-0.9, 0., 0.), 0.5).unwrap()));
+    assert!(joined.contains(&Sphere::new(Point3::new(0.9, 0., 0.), 0.5).unwrap()));
+    assert!(!joined.contains(&Sphere::new(Point3::new(0., 0.9, 0.), 0.5).unwrap()));
+    assert!(joined.is_disjoint(&Sphere::new(Point3::new(2., 0., 0.), 0.4).unwrap()));
+}
+
+#[test]
+fn intersection_of_spheres() {
+    let one = Sphere::new(Point3::new(-0.5, 0., 0.), 1.).unwrap();
+    let two = Sphere::new(Point3::new(0.5, 0., 0.), 1.).unwrap();
+    let far = Sphere::new(Point3::new(5., 0., 0.), 1.).unwrap();
+
+    let lens = Intersection::new(one.clone(), two).unwrap();
+    assert!((lens.volume() - 5. / 12. * PI).abs() < 0.02 * 5. / 12. * PI);
+    assert!(lens.contains(&Sphere::new(Point3::origin(), 0.49).unwrap()));
+    assert!(!lens.contains(&Sphere::new(Point3::new(0.3, 0., 0.), 0.3).unwrap()));
+
+    let empty = Intersection::new(one, far).unwrap();
+    assert_eq!(empty.volume(), 0.);
+    assert_eq!(empty.bounding_box(), None);
+}
+
+#[test]
+fn cuboid_with_hole() {
+    let block = Cuboid::new(2., 2., 2.).unwrap();
+    let hole = Cylinder::new(0.5, 6.).unwrap();
+    let drilled = Difference::new(block, hole).unwrap();
+
+    let expected = 64. - PI;
+    assert!((drilled.volume() - expected).abs() < 0.01 * expected);
+    assert!(drilled.contains(&Sphere::new(Point3::new(1., 1., 0.), 0.5).unwrap()));
+    assert!(drilled.contains(&Sphere::new(Point3::new(0.8, 0., 0.), 0.29).unwrap()));
+    assert!(!drilled.contains(&Sphere::new(Point3::new(0.8, 0., 0.), 0.31).unwrap()));
+    assert!(!drilled.contains(&Sphere::new(Point3::new(0., 0., 1.5), 0.1).unwrap()));
+    // Entirely within the hole, so disjoint from what is left
+    assert!(drilled.is_disjoint(&Sphere::new(Point3::new(0., 0., 1.5), 0.1).unwrap()));
+}
+
+#[test]
+fn in_sdf() {
+    let sdf = |p: Point3<f32>| p.coords.norm() - 2.;
+    let min = Point3::new(-2.5, -2.5, -2.5);
+    let max = Point3::new(2.5, 2.5, 2.5);
+    let container = SdfContainer::new(sdf, min, max, Integrator::Grid { resolution: 100 }).unwrap();
+    let analytic = 4. / 3. * PI * 8.;
+    assert!((container.volume() - analytic).abs() / analytic < 0.01);
+    assert!(container.contains(&Sphere::new(Point3::new(1., 0., 0.), 0.9).unwrap()));
+    assert!(!container.contains(&Sphere::new(Point3::new(1., 0., 0.), 1.1).unwrap()));
+    assert!(container.is_disjoint(&Sphere::new(Point3::new(3., 0., 0.), 0.9).unwrap()));
+    assert!(!container.is_disjoint(&Sphere::new(Point3::new(3., 0., 0.), 1.1).unwrap()));
+
+    let boxed: Box<dyn Fn(Point3<f32>) -> f32> = Box::new(sdf);
+    let container =
+        SdfContainer::new(boxed, min, max, Integrator::MonteCarlo { samples: 100_000 }).unwrap();
+    assert!((container.volume() - analytic).abs() / analytic < 0.02);
+    assert!(SdfContainer::new(sdf, max, min, Integrator::Grid { resolution: 10 }).is_err());
+}
+
+#[test]
+fn sdf_without_samples() {
+    let sdf = |p: Point3<f32>| p.coords.norm() - 2.;
+    let min = Point3::new(-2.5, -2.5, -2.5);
+    let max = Point3::new(2.5, 2.5, 2.5);
+
+    assert!(SdfContainer::new(sdf, min, max, Integrator::Grid { resolution: 0 }).is_err());
+    assert!(SdfContainer::new(sdf, min, max, Integrator::MonteCarlo { samples: 0 }).is_err());
+
+    // Nor can there be more cells than can be counted
+    let overflow = Integrator::Grid {
+        resolution: usize::MAX,
+    };
+    assert!(matches!(
+        SdfContainer::new(sdf, min, max, overflow),
+        Err(SphericalCowError::TooManyCells)
+    ));
+
+    // Combinations which need to estimate a volume are held to the same standard
+    let cube = Cuboid::new(1., 1., 1.).unwrap();
+    let ball = Sphere::new(Point3::new(1., 0., 0.), 1.).unwrap();
+    let empty = Integrator::MonteCarlo { samples: 0 };
+    assert!(Union::with_integrator(cube, ball, empty).is_err());
+}
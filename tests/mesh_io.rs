@@ -0,0 +1,98 @@
+#![cfg(feature = "mesh-io")]
+
+extern crate float_cmp;
+extern crate nalgebra;
+extern crate spherical_cow;
+
+use float_cmp::ApproxEqUlps;
+use nalgebra::Point3;
+use spherical_cow::errors::SphericalCowError;
+use spherical_cow::shapes::TriMesh;
+use spherical_cow::Container;
+
+/// Corners of a tetrahedron's faces, wound with outward normals.
+fn tetrahedron() -> Vec<[[f32; 3]; 3]> {
+    let v = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+    [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]]
+        .iter()
+        .map(|&[a, b, c]| [v[a], v[b], v[c]])
+        .collect()
+}
+
+fn ascii_stl(faces: &[[[f32; 3]; 3]]) -> String {
+    let mut stl = String::from("solid tetrahedron\n");
+    for face in faces.iter() {
+        stl.push_str("  facet normal 0 0 0\n    outer loop\n");
+        for corner in face.iter() {
+            stl.push_str(&format!(
+                "      vertex {} {} {}\n",
+                corner[0], corner[1], corner[2]
+            ));
+        }
+        stl.push_str("    endloop\n  endfacet\n");
+    }
+    stl.push_str("endsolid tetrahedron\n");
+    stl
+}
+
+fn binary_stl(faces: &[[[f32; 3]; 3]]) -> Vec<u8> {
+    // Binary files are free to start their header with `solid` too
+    let mut stl = b"solid but binary".to_vec();
+    stl.resize(80, 0);
+    stl.extend_from_slice(&(faces.len() as u32).to_le_bytes());
+    for face in faces.iter() {
+        stl.extend_from_slice(&[0; 12]);
+        for coord in face.iter().flat_map(|corner| corner.iter()) {
+            stl.extend_from_slice(&coord.to_le_bytes());
+        }
+        stl.extend_from_slice(&[0; 2]);
+    }
+    stl
+}
+
+#[test]
+fn load_emerald_obj() {
+    let emerald = TriMesh::load_obj("examples/objects/emerald.obj").unwrap();
+
+    assert_eq!(emerald.vertices().len(), 72);
+    assert_eq!(emerald.faces().len(), 140);
+    assert!(emerald.volume().approx_eq_ulps(&2354.709, 2));
+}
+
+#[test]
+fn read_obj_rejects_quads() {
+    let square = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n";
+    let err = TriMesh::read_obj(square.as_bytes()).unwrap_err();
+
+    assert!(matches!(err, SphericalCowError::NonTriangularFace));
+}
+
+#[test]
+fn read_stl_formats_agree() {
+    let ascii = TriMesh::read_stl(ascii_stl(&tetrahedron()).as_bytes()).unwrap();
+    let binary = TriMesh::read_stl(&binary_stl(&tetrahedron())[..]).unwrap();
+    assert_eq!(ascii, binary);
+    assert_eq!(ascii.vertices().len(), 4);
+    assert!((ascii.volume() - 1. / 6.).abs() < 1e-6);
+    assert_eq!(ascii.vertices()[3], Point3::new(0., 0., 1.));
+}
+
+#[test]
+fn read_stl_errors() {
+    let mut faces = tetrahedron();
+    faces.pop();
+    let err = TriMesh::read_stl(ascii_stl(&faces).as_bytes()).unwrap_err();
+    assert!(matches!(err, SphericalCowError::OpenMesh));
+
+    let quad = "solid quad\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 1 1 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid quad\n";
+    let err = TriMesh::read_stl(quad.as_bytes()).unwrap_err();
+    assert!(matches!(err, SphericalCowError::NonTriangularFace));
+
+    let garbled = "solid garbled\nfacet normal 0 0 1\nouter loop\nvertex 0 zero 0\n";
+    let err = TriMesh::read_stl(garbled.as_bytes()).unwrap_err();
+    assert!(matches!(err, SphericalCowError::MalformedMesh));
+
+    let truncated = &binary_stl(&tetrahedron())[..150];
+    let err = TriMesh::read_stl(truncated).unwrap_err();
+    assert!(matches!(err, SphericalCowError::MalformedMesh));
+}
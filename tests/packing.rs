@@ -7,8 +7,15 @@ use float_cmp::ApproxEqUlps;
 use nalgebra::core::Matrix3;
 use nalgebra::Point3;
 use rand::distributions::Uniform;
-use spherical_cow::shapes::Sphere;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use spherical_cow::builder::{Control, PackingBuilder, PackingObserver, Progress, Termination};
+use spherical_cow::shapes::{
+    Cone, Cuboid, Cylinder, CylindricalShell, Difference, Integrator, PeriodicCuboid, SdfContainer,
+    Sphere, SphericalShell,
+};
 use spherical_cow::*;
+use std::time::Duration;
 
 #[test]
 fn packed_volume_create_ok() {
@@ -76,3 +83,414 @@ fn packing_statistics() {
             )
     );
 }
+
+#[test]
+fn packed_volume_seeded_reproducible() {
+    let mut sizes = Uniform::new(0.1, 0.2);
+
+    let first =
+        PackedVolume::new_seeded(Sphere::new(Point3::origin(), 1.5).unwrap(), &mut sizes, 42)
+            .unwrap();
+    let second =
+        PackedVolume::new_seeded(Sphere::new(Point3::origin(), 1.5).unwrap(), &mut sizes, 42)
+            .unwrap();
+
+    assert!(first.spheres.len() > 3);
+    assert_eq!(first.spheres, second.spheres);
+}
+
+#[test]
+fn pack_spheres_with_rng_reproducible() {
+    let boundary = Cuboid::new(1.0, 1.2, 0.8).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+
+    let first =
+        pack_spheres_with_rng(&boundary, &mut sizes, &mut StdRng::seed_from_u64(7)).unwrap();
+    let second =
+        pack_spheres_with_rng(&boundary, &mut sizes, &mut StdRng::seed_from_u64(7)).unwrap();
+    let other =
+        pack_spheres_with_rng(&boundary, &mut sizes, &mut StdRng::seed_from_u64(8)).unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn packed_spheres_do_not_overlap() {
+    let boundary = Cuboid::new(1.5, 1.0, 1.2).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.25);
+    let packed = PackedVolume::new_seeded(boundary, &mut sizes, 3).unwrap();
+
+    for (idx, sphere) in packed.spheres.iter().enumerate() {
+        assert!(packed.container.contains(sphere));
+        // Spheres are placed tangent to one another, so allow for f32 rounding in the contact.
+        for other in packed.spheres[idx + 1..].iter() {
+            let gap =
+                nalgebra::distance(&sphere.center, &other.center) - sphere.radius - other.radius;
+            assert!(gap > -1e-5);
+        }
+    }
+}
+
+#[test]
+fn builder_stops_at_sphere_count() {
+    let boundary = Cuboid::new(2.0, 2.0, 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(boundary)
+        .max_spheres(50)
+        .seed(1)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::SphereCount);
+    assert_eq!(packing.packed.spheres.len(), 50);
+}
+
+#[test]
+fn builder_stops_at_volume_fraction() {
+    let boundary = Sphere::new(Point3::origin(), 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(boundary)
+        .volume_fraction(0.2)
+        .seed(2)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::VolumeFraction);
+    let fraction = packing.packed.volume_fraction();
+    assert!((0.2..0.21).contains(&fraction));
+}
+
+#[test]
+fn builder_stops_at_iterations() {
+    let boundary = Sphere::new(Point3::origin(), 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(boundary)
+        .max_iterations(10)
+        .seed(3)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::Iterations);
+    assert!(packing.packed.spheres.len() <= 13);
+}
+
+#[test]
+fn builder_stops_at_time_budget() {
+    let boundary = Sphere::new(Point3::origin(), 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(boundary)
+        .time_budget(Duration::from_secs(0))
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::TimeBudget);
+    assert_eq!(packing.packed.spheres.len(), 3);
+}
+
+#[test]
+fn builder_exhausts_front() {
+    let boundary = Sphere::new(Point3::origin(), 1.0).unwrap();
+    let mut sizes = Uniform::new(0.3, 0.5);
+    let packing = PackingBuilder::new(boundary)
+        .seed(4)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::FrontExhausted);
+}
+
+struct Recorder {
+    placed: usize,
+    exhausted: usize,
+    last: Option<Progress>,
+    cancel_after: Option<usize>,
+}
+
+impl PackingObserver for Recorder {
+    fn sphere_placed(&mut self, progress: &Progress) -> Control {
+        self.placed += 1;
+        self.last = Some(*progress);
+        if self.cancel_after == Some(self.placed) {
+            Control::Cancel
+        } else {
+            Control::Continue
+        }
+    }
+
+    fn sphere_exhausted(&mut self, progress: &Progress) -> Control {
+        self.exhausted += 1;
+        self.last = Some(*progress);
+        Control::Continue
+    }
+}
+
+#[test]
+fn observer_sees_every_event() {
+    let boundary = Sphere::new(Point3::origin(), 1.2).unwrap();
+    let mut sizes = Uniform::new(0.2, 0.3);
+    let mut recorder = Recorder {
+        placed: 0,
+        exhausted: 0,
+        last: None,
+        cancel_after: None,
+    };
+    let packing = PackingBuilder::new(boundary)
+        .observer(&mut recorder)
+        .seed(5)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::FrontExhausted);
+    let spheres = packing.packed.spheres.len();
+    // Every sphere, including the initial three, eventually leaves the front
+    assert_eq!(recorder.placed, spheres - 3);
+    assert_eq!(recorder.exhausted, spheres);
+    let last = recorder.last.unwrap();
+    assert_eq!(last.spheres, spheres);
+    assert_eq!(last.front, 0);
+    assert!(last
+        .volume_fraction
+        .approx_eq_ulps(&packing.packed.volume_fraction(), 4));
+}
+
+#[test]
+fn observer_cancels_run() {
+    let boundary = Sphere::new(Point3::origin(), 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let mut recorder = Recorder {
+        placed: 0,
+        exhausted: 0,
+        last: None,
+        cancel_after: Some(20),
+    };
+    let packing = PackingBuilder::new(boundary)
+        .observer(&mut recorder)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::Cancelled);
+    assert_eq!(packing.packed.spheres.len(), 23);
+}
+
+#[test]
+fn builder_resumes_from_checkpoint() {
+    let boundary = Sphere::new(Point3::origin(), 1.5).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let checkpoint = PackingBuilder::new(boundary)
+        .max_spheres(30)
+        .seed(6)
+        .pack(&mut sizes)
+        .unwrap();
+    assert_eq!(checkpoint.termination, Termination::SphereCount);
+    let before = checkpoint.packed.spheres.clone();
+
+    let resumed = checkpoint.resume().seed(7).pack(&mut sizes).unwrap();
+
+    assert_eq!(resumed.termination, Termination::FrontExhausted);
+    assert!(resumed.front.is_empty());
+    assert!(resumed.packed.spheres.len() > before.len());
+    assert_eq!(resumed.packed.spheres[..before.len()], before[..]);
+    for (idx, sphere) in resumed.packed.spheres.iter().enumerate() {
+        assert!(resumed.packed.container.contains(sphere));
+        for other in resumed.packed.spheres[idx + 1..].iter() {
+            let gap =
+                nalgebra::distance(&sphere.center, &other.center) - sphere.radius - other.radius;
+            assert!(gap > -1e-5);
+        }
+    }
+}
+
+#[test]
+fn builder_resumes_without_front() {
+    let boundary = Sphere::new(Point3::origin(), 1.5).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let partial = PackingBuilder::new(boundary)
+        .max_spheres(10)
+        .seed(8)
+        .pack(&mut sizes)
+        .unwrap();
+    let packed = PackedVolume::from_vec(partial.packed.spheres, partial.packed.container);
+
+    let resumed = PackingBuilder::resume(packed)
+        .seed(8)
+        .pack(&mut sizes)
+        .unwrap();
+
+    assert_eq!(resumed.termination, Termination::FrontExhausted);
+    assert!(resumed.packed.spheres.len() > 10);
+}
+
+#[test]
+fn builder_rejects_repeated_front() {
+    let boundary = Sphere::new(Point3::origin(), 1.5).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let partial = PackingBuilder::new(boundary)
+        .max_spheres(10)
+        .seed(8)
+        .pack(&mut sizes)
+        .unwrap();
+    let mut front = partial.front.clone();
+    front.push(front[0]);
+
+    let err = PackingBuilder::resume(partial.packed)
+        .front(front)
+        .pack(&mut sizes)
+        .unwrap_err();
+    assert!(matches!(err, errors::SphericalCowError::InvalidFront));
+}
+
+#[test]
+fn periodic_packing_wraps() {
+    let boundary = PeriodicCuboid::new(1.0, 1.0, 1.0).unwrap();
+    let period = boundary.period().unwrap();
+    let mut sizes = Uniform::new(0.1, 0.15);
+    let packed = PackedVolume::new_seeded(boundary, &mut sizes, 12).unwrap();
+
+    // Some spheres should straddle the cell faces
+    assert!(packed.spheres.iter().any(|s| s
+        .center
+        .coords
+        .iter()
+        .any(|c| c.abs() + s.radius > 1.0)));
+    for (idx, sphere) in packed.spheres.iter().enumerate() {
+        assert!(packed.container.contains(sphere));
+        for other in packed.spheres[idx + 1..].iter() {
+            // Distance to the closest periodic image of the other sphere
+            let delta =
+                (other.center - sphere.center).zip_map(&period, |d, p| d - p * (d / p).round());
+            let gap = delta.norm() - sphere.radius - other.radius;
+            assert!(gap > -1e-5);
+        }
+    }
+    // Contacts across faces count toward coordination, so an infinite periodic bed
+    // is far better connected than a walled box can be
+    assert!(packed.coordination_number() > 5.0);
+}
+
+#[test]
+fn packs_into_offset_sphere() {
+    let boundary = Sphere::new(Point3::new(-20., 15., 40.), 1.5).unwrap();
+    let mut sizes = Uniform::new(0.2, 0.3);
+    let packed = PackedVolume::new_seeded(boundary, &mut sizes, 13).unwrap();
+
+    assert!(packed.spheres.len() > 10);
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
+
+/// A box spanning [5, 7] along every axis, which only reports its bounds
+/// rather than a seed point.
+struct DistantBox;
+
+impl Container for DistantBox {
+    fn contains(&self, sphere: &Sphere) -> bool {
+        sphere
+            .center
+            .iter()
+            .all(|c| c - sphere.radius >= 5. && c + sphere.radius <= 7.)
+    }
+    fn volume(&self) -> f32 {
+        8.
+    }
+    fn bounding_box(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        Some((Point3::new(5., 5., 5.), Point3::new(7., 7., 7.)))
+    }
+}
+
+#[test]
+fn searches_bounding_box_for_seed() {
+    let mut sizes = Uniform::new(0.2, 0.3);
+    let packed = PackedVolume::new_seeded(DistantBox, &mut sizes, 3).unwrap();
+
+    assert!(packed.spheres.len() > 10);
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
+
+#[test]
+fn packs_into_cylinder() {
+    let boundary = Cylinder::new(1., 2.).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let packed = PackedVolume::new_seeded(boundary, &mut sizes, 5).unwrap();
+
+    assert!(packed.spheres.len() > 10);
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
+
+#[test]
+fn packs_into_cone() {
+    let boundary = Cone::new(1.5, 3.).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let packed = PackedVolume::new_seeded(boundary, &mut sizes, 8).unwrap();
+
+    assert!(packed.spheres.len() > 10);
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
+
+#[test]
+fn packs_into_shells() {
+    let mut sizes = Uniform::new(0.15, 0.2);
+    let boundary = SphericalShell::new(Point3::origin(), 1., 2.).unwrap();
+    let packing = PackingBuilder::new(boundary)
+        .max_spheres(100)
+        .seed(21)
+        .pack(&mut sizes)
+        .unwrap();
+    assert_eq!(packing.termination, Termination::SphereCount);
+    let packed = packing.packed;
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+
+    let boundary = CylindricalShell::new(1., 2., 2.).unwrap();
+    let packing = PackingBuilder::new(boundary)
+        .max_spheres(100)
+        .seed(21)
+        .pack(&mut sizes)
+        .unwrap();
+    assert_eq!(packing.termination, Termination::SphereCount);
+    let packed = packing.packed;
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
+
+#[test]
+fn packs_around_hole() {
+    // The seed point of the block lies in the hole, so packing must find its own
+    let block = Cuboid::new(1.5, 1.5, 1.5).unwrap();
+    let hole = Cylinder::new(0.5, 4.).unwrap();
+    let drilled = Difference::new(block, hole).unwrap();
+    let mut sizes = Uniform::new(0.15, 0.25);
+    let packing = PackingBuilder::new(drilled)
+        .max_spheres(100)
+        .seed(2)
+        .pack(&mut sizes)
+        .unwrap();
+    assert_eq!(packing.termination, Termination::SphereCount);
+
+    let packed = packing.packed;
+    let hole = packed.container.second();
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+    assert!(packed.spheres.iter().all(|s| hole.is_disjoint(s)));
+}
+
+#[test]
+fn packs_sdf_torus() {
+    // A torus about the z axis, which does not enclose the origin
+    let torus = |p: Point3<f32>| {
+        let ring = (p.x * p.x + p.y * p.y).sqrt() - 2.;
+        (ring * ring + p.z * p.z).sqrt() - 0.8
+    };
+    let container = SdfContainer::new(
+        torus,
+        Point3::new(-3., -3., -1.),
+        Point3::new(3., 3., 1.),
+        Integrator::Grid { resolution: 64 },
+    )
+    .unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(container)
+        .max_spheres(200)
+        .seed(4)
+        .pack(&mut sizes)
+        .unwrap();
+    assert_eq!(packing.termination, Termination::SphereCount);
+    let packed = packing.packed;
+    assert!(packed.spheres.iter().all(|s| packed.container.contains(s)));
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "rayon")]
+
+extern crate nalgebra;
+extern crate rand;
+extern crate spherical_cow;
+
+use nalgebra::Point3;
+use rand::distributions::Uniform;
+use spherical_cow::builder::{PackingBuilder, Termination};
+use spherical_cow::shapes::{Cuboid, Sphere};
+use spherical_cow::{Container, PackedVolume};
+
+fn assert_valid<C: Container>(packed: &PackedVolume<C>) {
+    for (idx, sphere) in packed.spheres.iter().enumerate() {
+        assert!(packed.container.contains(sphere));
+        // Spheres are placed tangent to one another, so allow for f32 rounding in the contact.
+        for other in packed.spheres[idx + 1..].iter() {
+            let gap =
+                nalgebra::distance(&sphere.center, &other.center) - sphere.radius - other.radius;
+            assert!(gap > -1e-5);
+        }
+    }
+}
+
+#[test]
+fn parallel_packing_valid() {
+    let boundary = Cuboid::new(1.5, 1.0, 1.2).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.25);
+    let packing = PackingBuilder::new(boundary)
+        .seed(10)
+        .pack_parallel(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::FrontExhausted);
+    assert!(packing.packed.volume_fraction() > 0.4);
+    assert_valid(&packing.packed);
+}
+
+#[test]
+fn parallel_packing_reproducible() {
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let first = PackingBuilder::new(Sphere::new(Point3::origin(), 1.5).unwrap())
+        .batch_size(16)
+        .seed(11)
+        .pack_parallel(&mut sizes)
+        .unwrap();
+    let second = PackingBuilder::new(Sphere::new(Point3::origin(), 1.5).unwrap())
+        .batch_size(16)
+        .seed(11)
+        .pack_parallel(&mut sizes)
+        .unwrap();
+
+    assert_eq!(first.packed.spheres, second.packed.spheres);
+    assert_valid(&first.packed);
+}
+
+#[test]
+fn parallel_packing_stops_at_sphere_count() {
+    let boundary = Cuboid::new(2.0, 2.0, 2.0).unwrap();
+    let mut sizes = Uniform::new(0.1, 0.2);
+    let packing = PackingBuilder::new(boundary)
+        .max_spheres(100)
+        .batch_size(32)
+        .pack_parallel(&mut sizes)
+        .unwrap();
+
+    assert_eq!(packing.termination, Termination::SphereCount);
+    assert_eq!(packing.packed.spheres.len(), 100);
+    assert_valid(&packing.packed);
+}
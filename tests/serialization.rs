@@ -1,10 +1,13 @@
 #![cfg(feature = "serde-1")]
 
 extern crate nalgebra;
+extern crate rand;
 extern crate serde_json;
 extern crate spherical_cow;
 
-use nalgebra::Point3;
+use nalgebra::{Isometry3, Point3, Vector3};
+use rand::distributions::Uniform;
+use spherical_cow::builder::{Packing, PackingBuilder, Termination};
 use spherical_cow::shapes::*;
 use spherical_cow::PackedVolume;
 
@@ -21,6 +24,15 @@ fn serialize_cuboid() {
     assert_eq!(cube, de_cube);
 }
 
+#[test]
+fn deserialize_cuboid_extent_count() {
+    let flat = "{\"half_extents\":[1.0,2.0]}";
+    assert!(serde_json::from_str::<Cuboid>(flat).is_err());
+    let hyper = "{\"half_extents\":[1.0,2.0,1.0,1.0]}";
+    assert!(serde_json::from_str::<Cuboid>(hyper).is_err());
+    assert!(Cuboid::from_vec(vec![1., 1.]).is_err());
+}
+
 #[test]
 fn serialize_sphere() {
     let sphere = Sphere::new(Point3::origin(), 1.0).unwrap();
@@ -49,3 +61,200 @@ fn serialize_packed_volume() {
     let ser_packed = serde_json::to_string(&de_packed).unwrap();
     assert_eq!(format!("{:?}", ser_packed), format!("{:?}", packed));
 }
+
+#[test]
+fn serialize_packing_checkpoint() {
+    let boundary = Cuboid::new(1.5, 1.5, 1.5).unwrap();
+    let mut sizes = Uniform::new(0.2, 0.3);
+    let checkpoint = PackingBuilder::new(boundary)
+        .max_spheres(15)
+        .seed(9)
+        .pack(&mut sizes)
+        .unwrap();
+
+    let ser_checkpoint = serde_json::to_string(&checkpoint).unwrap();
+    assert!(ser_checkpoint.ends_with("\"termination\":\"SphereCount\"}"));
+
+    let de_checkpoint: Packing<Cuboid> = serde_json::from_str(&ser_checkpoint).unwrap();
+    assert_eq!(de_checkpoint.packed.spheres, checkpoint.packed.spheres);
+    assert_eq!(de_checkpoint.front, checkpoint.front);
+    assert_eq!(de_checkpoint.termination, Termination::SphereCount);
+
+    let resumed = de_checkpoint.resume().pack(&mut sizes).unwrap();
+    assert_eq!(resumed.termination, Termination::FrontExhausted);
+    assert_eq!(resumed.packed.spheres[..15], checkpoint.packed.spheres[..]);
+}
+
+#[test]
+fn serialize_periodic_cuboid() {
+    let cube = PeriodicCuboid::new(1., 2., 1.).unwrap();
+    let ser_cube = serde_json::to_string(&cube).unwrap();
+    assert_eq!(
+        format!("{}", ser_cube),
+        format!("{{\"half_extents\":[1.0,2.0,1.0]}}")
+    );
+
+    let de_cube: PeriodicCuboid = serde_json::from_str(&ser_cube).unwrap();
+    assert_eq!(cube, de_cube);
+}
+
+#[test]
+fn deserialize_periodic_cuboid_extent_count() {
+    let flat = "{\"half_extents\":[1.0,2.0]}";
+    assert!(serde_json::from_str::<PeriodicCuboid>(flat).is_err());
+    let hyper = "{\"half_extents\":[1.0,2.0,1.0,1.0]}";
+    assert!(serde_json::from_str::<PeriodicCuboid>(hyper).is_err());
+}
+
+#[test]
+fn serialize_oriented_cuboid() {
+    let isometry = Isometry3::new(Vector3::new(1., -2., 3.), Vector3::new(0.3, 0., 0.5));
+    let cube = Cuboid::new(1., 2., 3.).unwrap().with_isometry(isometry);
+    let ser_cube = serde_json::to_string(&cube).unwrap();
+    assert!(ser_cube.contains("\"isometry\""));
+
+    let de_cube: Cuboid = serde_json::from_str(&ser_cube).unwrap();
+    assert_eq!(cube, de_cube);
+}
+
+#[test]
+fn serialize_transformed() {
+    let isometry = Isometry3::new(Vector3::new(1., -2., 3.), Vector3::new(0., 0.4, 0.));
+    let sphere = Sphere::new(Point3::origin(), 1.0).unwrap();
+    let transformed = Transformed::new(sphere, isometry, 2.5).unwrap();
+    let ser_transformed = serde_json::to_string(&transformed).unwrap();
+
+    let de_transformed: Transformed<Sphere> = serde_json::from_str(&ser_transformed).unwrap();
+    assert_eq!(transformed, de_transformed);
+}
+
+#[test]
+fn serialize_cylinder() {
+    let cylinder = Cylinder::new(1.5, 4.).unwrap();
+    let ser_cylinder = serde_json::to_string(&cylinder).unwrap();
+    assert_eq!(
+        format!("{}", ser_cylinder),
+        format!("{{\"radius\":1.5,\"height\":4.0}}")
+    );
+    let de_cylinder: Cylinder = serde_json::from_str(&ser_cylinder).unwrap();
+    assert_eq!(cylinder, de_cylinder);
+
+    let isometry = Isometry3::new(Vector3::new(0., 1., 0.), Vector3::new(0.2, 0.1, 0.));
+    let cylinder = cylinder.with_isometry(isometry);
+    let ser_cylinder = serde_json::to_string(&cylinder).unwrap();
+    let de_cylinder: Cylinder = serde_json::from_str(&ser_cylinder).unwrap();
+    assert_eq!(cylinder, de_cylinder);
+}
+
+#[test]
+fn serialize_capsule_cone_frustum() {
+    let capsule = Capsule::new(1., 2.).unwrap();
+    let ser_capsule = serde_json::to_string(&capsule).unwrap();
+    assert_eq!(
+        format!("{}", ser_capsule),
+        format!("{{\"radius\":1.0,\"height\":2.0}}")
+    );
+    let de_capsule: Capsule = serde_json::from_str(&ser_capsule).unwrap();
+    assert_eq!(capsule, de_capsule);
+
+    let isometry = Isometry3::new(Vector3::new(0., 1., 0.), Vector3::new(0.2, 0.1, 0.));
+    let cone = Cone::new(1., 3.).unwrap().with_isometry(isometry);
+    let ser_cone = serde_json::to_string(&cone).unwrap();
+    let de_cone: Cone = serde_json::from_str(&ser_cone).unwrap();
+    assert_eq!(cone, de_cone);
+
+    let frustum = Frustum::new(2., 1., 3.).unwrap();
+    let ser_frustum = serde_json::to_string(&frustum).unwrap();
+    assert_eq!(
+        format!("{}", ser_frustum),
+        format!("{{\"bottom_radius\":2.0,\"top_radius\":1.0,\"height\":3.0}}")
+    );
+    let de_frustum: Frustum = serde_json::from_str(&ser_frustum).unwrap();
+    assert_eq!(frustum, de_frustum);
+}
+
+#[test]
+fn serialize_ellipsoid() {
+    let ellipsoid = Ellipsoid::new(3., 2., 1.).unwrap();
+    let ser_ellipsoid = serde_json::to_string(&ellipsoid).unwrap();
+    assert_eq!(
+        format!("{}", ser_ellipsoid),
+        format!("{{\"semi_axes\":[3.0,2.0,1.0]}}")
+    );
+    let de_ellipsoid: Ellipsoid = serde_json::from_str(&ser_ellipsoid).unwrap();
+    assert_eq!(ellipsoid, de_ellipsoid);
+
+    let isometry = Isometry3::new(Vector3::new(0., 1., 0.), Vector3::new(0.2, 0.1, 0.));
+    let ellipsoid = ellipsoid.with_isometry(isometry);
+    let ser_ellipsoid = serde_json::to_string(&ellipsoid).unwrap();
+    let de_ellipsoid: Ellipsoid = serde_json::from_str(&ser_ellipsoid).unwrap();
+    assert_eq!(ellipsoid, de_ellipsoid);
+}
+
+#[test]
+fn serialize_shells() {
+    let shell = SphericalShell::new(Point3::new(1., 0., 0.), 1., 2.).unwrap();
+    let ser_shell = serde_json::to_string(&shell).unwrap();
+    assert_eq!(
+        format!("{}", ser_shell),
+        format!("{{\"center\":[1.0,0.0,0.0],\"inner_radius\":1.0,\"outer_radius\":2.0}}")
+    );
+    let de_shell: SphericalShell = serde_json::from_str(&ser_shell).unwrap();
+    assert_eq!(shell, de_shell);
+
+    let isometry = Isometry3::new(Vector3::new(0., 1., 0.), Vector3::new(0.2, 0.1, 0.));
+    let shell = CylindricalShell::new(1., 2., 3.)
+        .unwrap()
+        .with_isometry(isometry);
+    let ser_shell = serde_json::to_string(&shell).unwrap();
+    let de_shell: CylindricalShell = serde_json::from_str(&ser_shell).unwrap();
+    assert_eq!(shell, de_shell);
+}
+
+#[test]
+fn serialize_combinations() {
+    let block = Cuboid::new(2., 2., 2.).unwrap();
+    let hole = Cylinder::new(0.5, 6.).unwrap();
+    let drilled = Difference::new(block, hole).unwrap();
+    let ser_drilled = serde_json::to_string(&drilled).unwrap();
+    assert_eq!(
+        format!("{}", ser_drilled),
+        format!("{{\"first\":{{\"half_extents\":[2.0,2.0,2.0]}},\"second\":{{\"radius\":0.5,\"height\":6.0}}}}")
+    );
+    let de_drilled: Difference<Cuboid, Cylinder> = serde_json::from_str(&ser_drilled).unwrap();
+    assert_eq!(drilled, de_drilled);
+
+    let one = Sphere::new(Point3::new(-0.5, 0., 0.), 1.).unwrap();
+    let two = Sphere::new(Point3::new(0.5, 0., 0.), 1.).unwrap();
+    let union = Union::new(one.clone(), two.clone()).unwrap();
+    let ser_union = serde_json::to_string(&union).unwrap();
+    let de_union: Union<Sphere, Sphere> = serde_json::from_str(&ser_union).unwrap();
+    assert_eq!(union, de_union);
+
+    let lens = Intersection::new(one, two).unwrap();
+    let ser_lens = serde_json::to_string(&lens).unwrap();
+    let de_lens: Intersection<Sphere, Sphere> = serde_json::from_str(&ser_lens).unwrap();
+    assert_eq!(lens, de_lens);
+}
+
+#[test]
+fn serialize_trimesh() {
+    let vertices = vec![
+        Point3::origin(),
+        Point3::new(1., 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(0., 0., 1.),
+    ];
+    let mesh = TriMesh::new(vertices, vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]]).unwrap();
+    let ser_mesh = serde_json::to_string(&mesh).unwrap();
+    assert_eq!(
+        format!("{}", ser_mesh),
+        format!("{{\"vertices\":[[0.0,0.0,0.0],[1.0,0.0,0.0],[0.0,1.0,0.0],[0.0,0.0,1.0]],\"faces\":[[0,2,1],[0,1,3],[1,2,3],[0,3,2]]}}")
+    );
+    let de_mesh: TriMesh = serde_json::from_str(&ser_mesh).unwrap();
+    assert_eq!(mesh, de_mesh);
+
+    // Meshes are validated as they are read
+    let open = "{\"vertices\":[[0.0,0.0,0.0],[1.0,0.0,0.0],[0.0,1.0,0.0]],\"faces\":[[0,1,2]]}";
+    assert!(serde_json::from_str::<TriMesh>(open).is_err());
+}